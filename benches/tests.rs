@@ -0,0 +1,156 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use nistrs::generators::{Generator, Lcg};
+use nistrs::prelude::*;
+
+const N_BITS: usize = 1_000_000;
+
+fn counting_tests(c: &mut Criterion) {
+    let data = Lcg::new().generate(N_BITS);
+
+    let mut group = c.benchmark_group("1Mbit");
+    group.sample_size(20);
+    group.bench_function("frequency", |b| b.iter(|| frequency_test(black_box(&data))));
+    group.bench_function("block_frequency", |b| {
+        b.iter(|| block_frequency_test(black_box(&data), 128))
+    });
+    group.bench_function("runs", |b| b.iter(|| runs_test(black_box(&data))));
+    group.bench_function("longest_run_of_ones", |b| {
+        b.iter(|| longest_run_of_ones_test(black_box(&data)))
+    });
+    group.bench_function("rank", |b| b.iter(|| rank_test(black_box(&data))));
+    group.bench_function("serial", |b| b.iter(|| serial_test(black_box(&data), 16)));
+    group.bench_function("approximate_entropy", |b| {
+        b.iter(|| approximate_entropy_test(black_box(&data), 10))
+    });
+    group.finish();
+}
+
+/// Bit-by-bit counting loops, which the word-level paths replaced, on the same data as `1Mbit`.
+///
+/// Every loop gives the same statistic as the test it is compared with, which is checked before the benchmark.
+fn bitwise_baseline(c: &mut Criterion) {
+    let data = Lcg::new().generate(N_BITS);
+    let statistic = |report: TestReport, name| report.statistic(name).unwrap();
+
+    let chi2 = statistic(block_frequency_test_report(&data, 128).unwrap(), "chi2");
+    assert!((bitwise::block_frequency_chi2(&data, 128) - chi2).abs() < 1e-9);
+    let v_obs = statistic(runs_test_report(&data).unwrap(), "v_obs");
+    assert_eq!(bitwise::runs(&data) as f64, v_obs);
+    let nu = longest_run_of_ones_test_report(&data).unwrap().observed;
+    assert_eq!(bitwise::longest_run_counts(&data), nu);
+    let psi2 = statistic(serial_test_report(&data, 16).unwrap()[0].clone(), "psi2_m");
+    assert!((bitwise::psi2(&data, 16) - psi2).abs() < 1e-6);
+    let phi = statistic(approximate_entropy_test_report(&data, 10).unwrap(), "phi_m");
+    assert!((bitwise::phi(&data, 10) - phi).abs() < 1e-9);
+
+    let mut group = c.benchmark_group("1Mbit bitwise");
+    group.sample_size(10);
+    group.bench_function("block_frequency", |b| {
+        b.iter(|| bitwise::block_frequency_chi2(black_box(&data), 128))
+    });
+    group.bench_function("runs", |b| b.iter(|| bitwise::runs(black_box(&data))));
+    group.bench_function("longest_run_of_ones", |b| {
+        b.iter(|| bitwise::longest_run_counts(black_box(&data)))
+    });
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            (14..=16)
+                .map(|m| bitwise::psi2(black_box(&data), m))
+                .sum::<f64>()
+        })
+    });
+    group.bench_function("approximate_entropy", |b| {
+        b.iter(|| bitwise::phi(black_box(&data), 10) - bitwise::phi(black_box(&data), 11))
+    });
+    group.finish();
+}
+
+mod bitwise {
+    use nistrs::BitsData;
+
+    pub fn block_frequency_chi2(data: &BitsData, m: usize) -> f64 {
+        let mut sum = 0_f64;
+        for i in 0..data.len() / m {
+            let mut block_sum = 0_usize;
+            for j in 0..m {
+                block_sum += data[i * m + j] as usize;
+            }
+
+            sum += ((block_sum as f64) / (m as f64) - 0.5).powi(2);
+        }
+
+        4_f64 * (m as f64) * sum
+    }
+
+    pub fn runs(data: &BitsData) -> usize {
+        let mut v = 1_usize;
+        for i in 1..data.len() {
+            if data[i] != data[i - 1] {
+                v += 1;
+            }
+        }
+
+        v
+    }
+
+    /// Numbers of 10000-bit blocks with the longest run of ones `<= 10`, `11`, ..., `>= 16`.
+    pub fn longest_run_counts(data: &BitsData) -> Vec<f64> {
+        let m = 10000;
+        let mut nu = vec![0_f64; 7];
+        for i in 0..data.len() / m {
+            let mut max_runs = 0_usize;
+            let mut cur_runs = 0_usize;
+            for j in 0..m {
+                if data[i * m + j] {
+                    cur_runs += 1;
+                    max_runs = max_runs.max(cur_runs);
+                } else {
+                    cur_runs = 0;
+                }
+            }
+
+            nu[max_runs.clamp(10, 16) - 10] += 1_f64;
+        }
+
+        nu
+    }
+
+    /// Counts of overlapping `m`-bit patterns with wrap-around, indexed by `% n`.
+    fn pattern_counts(data: &BitsData, m: usize) -> Vec<usize> {
+        let n = data.len();
+        let mut p = vec![0_usize; 1 << m];
+        for i in 0..n {
+            let mut k = 0_usize;
+            for j in 0..m {
+                k = (k << 1) | data[(i + j) % n] as usize;
+            }
+
+            p[k] += 1;
+        }
+
+        p
+    }
+
+    pub fn psi2(data: &BitsData, m: usize) -> f64 {
+        let n = data.len() as f64;
+        let sum = pattern_counts(data, m)
+            .iter()
+            .map(|x| (x * x) as f64)
+            .sum::<f64>();
+
+        sum * ((1 << m) as f64) / n - n
+    }
+
+    pub fn phi(data: &BitsData, m: usize) -> f64 {
+        let n = data.len() as f64;
+        pattern_counts(data, m)
+            .iter()
+            .filter(|x| **x != 0)
+            .map(|x| (*x as f64) / n * ((*x as f64) / n).ln())
+            .sum()
+    }
+}
+
+criterion_group!(benches, counting_tests, bitwise_baseline);
+criterion_main!(benches);
@@ -1,5 +1,3 @@
-use statrs::function::gamma::gamma_ur;
-
 use super::*;
 
 /// Approximate Entropy Test.
@@ -8,8 +6,35 @@ use super::*;
 /// overlapping blocks of two consecutive/adjacent lengths (m and m+1) against the expected result for a
 /// random sequence.
 /// `m` the length of each block – in this case, the first block length used in the test. m+1 is the second block length used.
-pub fn approximate_entropy_test(data: &BitsData, m: usize) -> TestResultT {
+/// # Errors
+/// [`Error::TooFewBits`] if the sequence is shorter than 2 bits, [`Error::InvalidParameter`] if `2^(m + 1)` is
+/// greater than the length of the sequence.
+pub fn approximate_entropy_test<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<TestResultT, Error> {
+    approximate_entropy_test_report(data, m).map(|x| x.result())
+}
+
+/// Approximate Entropy Test with detailed result.
+///
+/// Statistics: `phi_m`, `phi_m1` - values of `ϕ` for `m` and `m + 1`, `ap_en` and `chi2`.
+/// See [`approximate_entropy_test`].
+pub fn approximate_entropy_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<TestReport, Error> {
+    let data = data.into();
+    check_len(&data, 2)?;
+
     let n = data.len();
+    let max_m = n.ilog2() as usize - 1;
+    if m > max_m {
+        return Err(Error::InvalidParameter {
+            name: "m",
+            allowed: format!("0..={}", max_m),
+        });
+    }
 
     let mut ap_en = [f64::default(); 2];
     let mut r = usize::default();
@@ -21,30 +46,11 @@ pub fn approximate_entropy_test(data: &BitsData, m: usize) -> TestResultT {
             continue;
         }
 
-        let pow_len = 2_usize.pow(block_size as u32 + 1) - 1;
-        let mut p = vec![usize::default(); pow_len];
-
-        for i in 0..n {
-            let mut k = 1_usize;
-            for j in 0..block_size {
-                k <<= 1;
-                if data[(i + j) % n] {
-                    k += 1;
-                }
-            }
-
-            p[k - 1] += 1;
-        }
-
-        let pow_const = 2_usize.pow(block_size as u32);
-        let mut index = pow_const - 1;
         let mut sum = f64::default();
-        for _ in 0..pow_const {
-            if p[index] > 0 {
-                sum += (p[index] as f64) * ((p[index] as f64) / (n as f64)).ln();
+        for i in data.cyclic_pattern_counts(block_size) {
+            if i > 0 {
+                sum += (i as f64) * ((i as f64) / (n as f64)).ln();
             }
-
-            index += 1;
         }
 
         sum /= n as f64;
@@ -54,7 +60,14 @@ pub fn approximate_entropy_test(data: &BitsData, m: usize) -> TestResultT {
 
     let apen = ap_en[0] - ap_en[1];
     let chi2 = 2_f64 * (n as f64) * (2_f64.ln() - apen);
-    let p = gamma_ur(2_f64.powi(m as i32 - 1), chi2 / 2_f64);
+    let p = igamc(2_f64.powi(m as i32 - 1), chi2 / 2_f64);
 
-    (p >= TEST_THRESHOLD, p)
+    Ok(TestReport::new("ApproximateEntropy", p)
+        .with_parameter("m", m as f64)
+        .with_parameter("n", n as f64)
+        .with_statistic("phi_m", ap_en[0])
+        .with_statistic("phi_m1", ap_en[1])
+        .with_statistic("ap_en", apen)
+        .with_statistic("chi2", chi2)
+        .with_degrees_of_freedom(2_f64.powi(m as i32)))
 }
@@ -0,0 +1,497 @@
+extern crate rayon;
+
+use std::io::{self, Write};
+
+use rayon::prelude::*;
+
+use super::*;
+use crate::battery::{Battery, BatteryConfig, BatteryReport, TestKind};
+
+/// Minimal `P-value` of the uniformity test, for which `P-values` are considered to be uniformly distributed.
+pub const UNIFORMITY_THRESHOLD: f64 = 0.0001;
+
+/// Summary of one sub-test (e.g. one template or state) over all sequences.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubTestSummary {
+    /// The test.
+    pub test: TestKind,
+    /// Index of sub-test within the test.
+    pub index: usize,
+    /// `P-values` of all sequences, for which the test is applicable.
+    pub p_values: Vec<f64>,
+    /// Number of `P-values` in every of ten intervals `[0, 0.1)`, `[0.1, 0.2)`, ..., `[0.9, 1]`.
+    pub histogram: [usize; 10],
+    /// `P-value` of the chi-square test for uniformity of `P-values` (`P-value_T`).
+    /// `None` if there are less than 10 sequences.
+    pub uniformity: Option<f64>,
+    /// Number of sequences passed the test.
+    pub passed: usize,
+    /// Minimal and maximal acceptable number of passed sequences, not rounded.
+    pub passed_range: (f64, f64),
+}
+
+impl SubTestSummary {
+    fn new(test: TestKind, index: usize, p_values: Vec<f64>, alpha: f64) -> Self {
+        let sample_size = p_values.len();
+
+        let mut histogram = [0_usize; 10];
+        for p in p_values.iter() {
+            histogram[((p * 10_f64).floor() as usize).min(9)] += 1;
+        }
+
+        // As in the reference implementation, expected count of bin is rounded down.
+        let exp_count = sample_size / 10;
+        let uniformity = match exp_count {
+            0 => None,
+            _ => {
+                let chi2 = histogram
+                    .iter()
+                    .map(|x| ((*x as f64) - (exp_count as f64)).powi(2) / (exp_count as f64))
+                    .sum::<f64>();
+
+                Some(igamc(9_f64 / 2_f64, chi2 / 2_f64))
+            }
+        };
+
+        SubTestSummary {
+            test,
+            index,
+            passed: p_values.iter().filter(|x| is_passed(**x, alpha)).count(),
+            passed_range: proportion_range(sample_size, alpha),
+            p_values,
+            histogram,
+            uniformity,
+        }
+    }
+
+    /// Return number of sequences, for which the test is applicable.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.p_values.len()
+    }
+
+    /// Return proportion of sequences passed the test.
+    #[inline]
+    pub fn proportion(&self) -> f64 {
+        (self.passed as f64) / (self.total() as f64)
+    }
+
+    /// Check that proportion of passed sequences is in the acceptable range.
+    #[inline]
+    pub fn proportion_passed(&self) -> bool {
+        let passed = self.passed as f64;
+        self.total() != 0 && passed >= self.passed_range.0 && passed <= self.passed_range.1
+    }
+
+    /// Check that `P-values` are uniformly distributed.
+    #[inline]
+    pub fn uniformity_passed(&self) -> bool {
+        self.uniformity.is_some_and(|x| x >= UNIFORMITY_THRESHOLD)
+    }
+}
+
+/// Return minimal and maximal acceptable number of passed sequences for `sample_size` sequences.
+///
+/// The acceptable proportion is `(1 - α) ± 3·sqrt(α(1 - α) / sample_size)`. As in the reference
+/// implementation, bounds are not rounded: the number of passed sequences is compared with them as is,
+/// so 980 of 1000 sequences is not enough for `α = 0.01`.
+/// # Example
+/// ```
+/// use nistrs::assessment::proportion_range;
+///
+/// let (min, max) = proportion_range(1000, 0.01);
+/// assert!((min - 980.560720).abs() < 1e-6);
+/// assert!((max - 999.439280).abs() < 1e-6);
+/// assert!(980_f64 < min);
+/// ```
+pub fn proportion_range(sample_size: usize, alpha: f64) -> (f64, f64) {
+    if sample_size == 0 {
+        return (0_f64, 0_f64);
+    }
+
+    let p_hat = 1_f64 - alpha;
+    let delta = 3_f64 * (p_hat * alpha / (sample_size as f64)).sqrt();
+
+    (
+        ((p_hat - delta) * (sample_size as f64)).max(0_f64),
+        (p_hat + delta) * (sample_size as f64),
+    )
+}
+
+/// Result of the assessment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssessmentReport {
+    /// Significance level.
+    pub alpha: f64,
+    /// Length of every sequence.
+    pub stream_len: usize,
+    /// Results of the battery for every sequence.
+    pub sequences: Vec<BatteryReport>,
+    /// Summaries of every sub-test in order of [`BatteryConfig::tests`].
+    pub summaries: Vec<SubTestSummary>,
+}
+
+impl AssessmentReport {
+    /// Check that proportion and uniformity of `P-values` are acceptable for every sub-test and no test failed
+    /// with an error on any sequence. Sub-tests, which are not applicable to any sequence, are skipped.
+    pub fn passed(&self) -> bool {
+        self.sequences.iter().all(|x| {
+            x.errors()
+                .all(|x| matches!(x.1, Error::NotApplicable { .. }))
+        }) && self
+            .summaries
+            .iter()
+            .filter(|x| x.total() != 0)
+            .all(|x| x.proportion_passed() && (x.uniformity.is_none() || x.uniformity_passed()))
+    }
+
+    /// Write the report in the layout of `finalAnalysisReport.txt` of the reference implementation.
+    ///
+    /// `generator` is the name of the tested generator or file, it is printed in the header.
+    /// # Example
+    /// ```
+    /// use nistrs::assessment::Assessment;
+    /// use nistrs::battery::{BatteryConfig, TestKind};
+    /// use nistrs::BitsData;
+    ///
+    /// let data = BitsData::from_binary((0..20000_u32).map(|x| (x * x / 7 + x) as u8).collect());
+    /// let config = BatteryConfig {
+    ///     tests: vec![TestKind::Frequency],
+    ///     ..Default::default()
+    /// };
+    /// let report = Assessment::new(config, 16000, 10).unwrap().run(&data).unwrap();
+    ///
+    /// let mut out = Vec::new();
+    /// report.write_final_analysis_report(&mut out, "data.bin").unwrap();
+    /// assert!(String::from_utf8(out).unwrap().contains("   generator is <data.bin>\n"));
+    /// ```
+    pub fn write_final_analysis_report<W: Write>(
+        &self,
+        out: &mut W,
+        generator: &str,
+    ) -> io::Result<()> {
+        const LINE: &str =
+            "------------------------------------------------------------------------------";
+        const DASHED_LINE: &str =
+            "- - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -";
+
+        writeln!(out, "{}", LINE)?;
+        writeln!(
+            out,
+            "RESULTS FOR THE UNIFORMITY OF P-VALUES AND THE PROPORTION OF PASSING SEQUENCES"
+        )?;
+        writeln!(out, "{}", LINE)?;
+        writeln!(out, "   generator is <{}>", generator)?;
+        writeln!(out, "{}", LINE)?;
+        writeln!(
+            out,
+            " C1  C2  C3  C4  C5  C6  C7  C8  C9 C10  P-VALUE  PROPORTION  STATISTICAL TEST"
+        )?;
+        writeln!(out, "{}", LINE)?;
+
+        for summary in self.summaries.iter() {
+            for count in summary.histogram.iter() {
+                write!(out, "{:3} ", count)?;
+            }
+
+            match summary.uniformity {
+                None => write!(out, "    ----    ")?,
+                Some(v) if v < UNIFORMITY_THRESHOLD => write!(out, " {:8.6} * ", v)?,
+                Some(v) => write!(out, " {:8.6}   ", v)?,
+            }
+
+            let name = summary.test.name();
+            if summary.total() == 0 {
+                writeln!(out, " ------     {}", name)?;
+            } else {
+                let passed = format!("{:4}/{:<4}", summary.passed, summary.total());
+                if summary.proportion_passed() {
+                    writeln!(out, "{}    {}", passed, name)?;
+                } else {
+                    writeln!(out, "{} *  {}", passed, name)?;
+                }
+            }
+        }
+
+        write!(out, "\n\n{}\n", DASHED_LINE)?;
+
+        let is_excursion = |x: &&SubTestSummary| {
+            matches!(
+                x.test,
+                TestKind::RandomExcursions | TestKind::RandomExcursionsVariant
+            )
+        };
+        let general_sample_size = self
+            .summaries
+            .iter()
+            .find(|x| !is_excursion(x))
+            .map(|x| x.total());
+        let excursion_sample_size = self.summaries.iter().find(is_excursion).map(|x| x.total());
+
+        match general_sample_size {
+            Some(0) => {
+                writeln!(
+                    out,
+                    "The minimum pass rate for each statistical test with the exception of the"
+                )?;
+                writeln!(out, "random excursion (variant) test is undefined.\n")?;
+            }
+            Some(v) => {
+                writeln!(
+                    out,
+                    "The minimum pass rate for each statistical test with the exception of the"
+                )?;
+                writeln!(
+                    out,
+                    "random excursion (variant) test is approximately = {} for a",
+                    proportion_range(v, self.alpha).0 as usize
+                )?;
+                writeln!(out, "sample size = {} binary sequences.\n", v)?;
+            }
+            None => {}
+        }
+
+        match excursion_sample_size {
+            Some(0) => writeln!(
+                out,
+                "The minimum pass rate for the random excursion (variant) test is undefined.\n"
+            )?,
+            Some(v) => {
+                writeln!(
+                    out,
+                    "The minimum pass rate for the random excursion (variant) test"
+                )?;
+                writeln!(
+                    out,
+                    "is approximately = {} for a sample size = {} binary sequences.\n",
+                    proportion_range(v, self.alpha).0 as usize,
+                    v
+                )?;
+            }
+            None => {}
+        }
+
+        writeln!(
+            out,
+            "For further guidelines construct a probability table using the MAPLE program"
+        )?;
+        writeln!(
+            out,
+            "provided in the addendum section of the documentation."
+        )?;
+        writeln!(out, "{}", DASHED_LINE)
+    }
+}
+
+/// Assessment of a generator according to section 4.2 of the NIST standard.
+///
+/// Input is split into `streams` sequences of `stream_len` bits, the battery is run on every sequence, then
+/// the proportion of passed sequences and the uniformity of `P-values` are checked for every sub-test.
+/// # Example
+/// ```
+/// use nistrs::assessment::Assessment;
+/// use nistrs::battery::{BatteryConfig, TestKind};
+/// use nistrs::BitsData;
+///
+/// let data = BitsData::from_binary((0..20000_u32).map(|x| (x * x / 7 + x) as u8).collect());
+/// let config = BatteryConfig {
+///     tests: vec![TestKind::Frequency, TestKind::CumulativeSums],
+///     ..Default::default()
+/// };
+///
+/// let report = Assessment::new(config, 16000, 10).unwrap().run(&data).unwrap();
+/// assert_eq!(report.summaries.len(), 3);
+/// assert_eq!(report.summaries[0].total(), 10);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assessment {
+    battery: Battery,
+    stream_len: usize,
+    streams: usize,
+}
+
+impl Assessment {
+    /// Create assessment of `streams` sequences of `stream_len` bits.
+    /// # Errors
+    /// [`Error::InvalidParameter`] if significance level is not in `(0, 1)`, `stream_len` or `streams` is `0` or
+    /// the total length `stream_len * streams` overflows `usize`.
+    pub fn new(config: BatteryConfig, stream_len: usize, streams: usize) -> Result<Self, Error> {
+        if stream_len == 0 {
+            return Err(Error::InvalidParameter {
+                name: "stream_len",
+                allowed: "1..".to_string(),
+            });
+        }
+
+        if streams == 0 || stream_len.checked_mul(streams).is_none() {
+            return Err(Error::InvalidParameter {
+                name: "streams",
+                allowed: format!("1..={}", usize::MAX / stream_len),
+            });
+        }
+
+        Ok(Assessment {
+            battery: Battery::new(config)?,
+            stream_len,
+            streams,
+        })
+    }
+
+    /// Return length of every sequence in bits.
+    #[inline]
+    pub fn stream_len(&self) -> usize {
+        self.stream_len
+    }
+
+    /// Return number of sequences.
+    #[inline]
+    pub fn streams(&self) -> usize {
+        self.streams
+    }
+
+    /// Run the assessment on the first `streams * stream_len` bits of `data`.
+    /// # Errors
+    /// [`Error::TooFewBits`] if `data` is too short.
+    pub fn run<'a>(&self, data: impl Into<BitsView<'a>>) -> Result<AssessmentReport, Error> {
+        let data = data.into();
+        // Does not overflow, checked by `new`.
+        let required = self.stream_len * self.streams;
+        if data.len() < required {
+            return Err(Error::TooFewBits {
+                required,
+                actual: data.len(),
+            });
+        }
+
+        let sequences: Vec<_> = (0..self.streams)
+            .into_par_iter()
+            .map(|i| {
+                let begin = i * self.stream_len;
+                self.battery.run(data.slice(begin..begin + self.stream_len))
+            })
+            .collect();
+
+        let alpha = self.battery.config().alpha;
+        let mut summaries = Vec::new();
+        for (i, test) in self.battery.config().tests.iter().enumerate() {
+            let results: Vec<_> = sequences
+                .iter()
+                .filter_map(|x| x.outcomes[i].result.as_ref().ok())
+                .collect();
+
+            // Random excursions tests are often not applicable to any sequence, their states are kept in
+            // summaries anyway, as the reference implementation does.
+            let n_sub_tests = match (results.iter().map(|x| x.len()).max(), test) {
+                (Some(v), _) => v,
+                (None, TestKind::RandomExcursions) => 8,
+                (None, TestKind::RandomExcursionsVariant) => 18,
+                (None, _) => 0,
+            };
+            for j in 0..n_sub_tests {
+                let p_values = results
+                    .iter()
+                    .filter_map(|x| x.get(j).map(|x| x.p_value))
+                    .collect();
+
+                summaries.push(SubTestSummary::new(*test, j, p_values, alpha));
+            }
+        }
+
+        Ok(AssessmentReport {
+            alpha,
+            stream_len: self.stream_len,
+            sequences,
+            summaries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proportion_bounds_are_not_rounded() {
+        let summary = |passed: usize, total: usize| {
+            let p_values = (0..total)
+                .map(|x| if x < passed { 0.5 } else { 0.001 })
+                .collect();
+            SubTestSummary::new(TestKind::Frequency, 0, p_values, 0.01)
+        };
+
+        assert!(!summary(8, 10).proportion_passed());
+        assert!(summary(9, 10).proportion_passed());
+        assert!(!summary(980, 1000).proportion_passed());
+        assert!(summary(981, 1000).proportion_passed());
+        assert!(summary(999, 1000).proportion_passed());
+        assert!(!summary(1000, 1000).proportion_passed());
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn final_analysis_report() {
+        let mut p_values: Vec<f64> = (0..10).map(|x| (x as f64) / 10_f64 + 0.05).collect();
+        p_values[0] = 0.001;
+
+        let report = AssessmentReport {
+            alpha: 0.01,
+            stream_len: 1000,
+            sequences: Vec::new(),
+            summaries: vec![
+                SubTestSummary::new(TestKind::Frequency, 0, p_values, 0.01),
+                SubTestSummary::new(TestKind::Runs, 0, vec![0.005; 10], 0.01),
+                SubTestSummary::new(TestKind::RandomExcursions, 0, vec![0.5, 0.7, 0.9, 0.3, 0.95], 0.01),
+                SubTestSummary::new(TestKind::RandomExcursions, 1, Vec::new(), 0.01),
+            ],
+        };
+
+        let mut out = Vec::new();
+        report.write_final_analysis_report(&mut out, "data/data.pi").unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+------------------------------------------------------------------------------
+RESULTS FOR THE UNIFORMITY OF P-VALUES AND THE PROPORTION OF PASSING SEQUENCES
+------------------------------------------------------------------------------
+   generator is <data/data.pi>
+------------------------------------------------------------------------------
+ C1  C2  C3  C4  C5  C6  C7  C8  C9 C10  P-VALUE  PROPORTION  STATISTICAL TEST
+------------------------------------------------------------------------------
+  1   1   1   1   1   1   1   1   1   1  1.000000      9/10      Frequency
+ 10   0   0   0   0   0   0   0   0   0  0.000000 *    0/10   *  Runs
+  0   0   0   1   0   1   0   1   0   2     ----       5/5       RandomExcursions
+  0   0   0   0   0   0   0   0   0   0     ----     ------     RandomExcursions
+
+
+- - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+The minimum pass rate for each statistical test with the exception of the
+random excursion (variant) test is approximately = 8 for a
+sample size = 10 binary sequences.
+
+The minimum pass rate for the random excursion (variant) test
+is approximately = 4 for a sample size = 5 binary sequences.
+
+For further guidelines construct a probability table using the MAPLE program
+provided in the addendum section of the documentation.
+- - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+");
+
+        let report = AssessmentReport {
+            summaries: vec![
+                SubTestSummary::new(TestKind::RandomExcursionsVariant, 0, Vec::new(), 0.01),
+                SubTestSummary::new(TestKind::Frequency, 0, vec![0.5; 100], 0.01),
+            ],
+            ..report
+        };
+
+        let mut out = Vec::new();
+        report.write_final_analysis_report(&mut out, "data/data.pi").unwrap();
+
+        assert!(String::from_utf8(out).unwrap().contains("\
+random excursion (variant) test is approximately = 96 for a
+sample size = 100 binary sequences.
+
+The minimum pass rate for the random excursion (variant) test is undefined.
+
+"));
+    }
+}
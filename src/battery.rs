@@ -0,0 +1,320 @@
+extern crate rayon;
+
+use std::str::FromStr;
+
+use rayon::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+/// Tests of the battery, in order of the reference implementation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TestKind {
+    Frequency,
+    BlockFrequency,
+    CumulativeSums,
+    Runs,
+    LongestRun,
+    Rank,
+    Fft,
+    NonOverlappingTemplate,
+    OverlappingTemplate,
+    Universal,
+    ApproximateEntropy,
+    RandomExcursions,
+    RandomExcursionsVariant,
+    Serial,
+    LinearComplexity,
+}
+
+impl TestKind {
+    /// All tests of the battery.
+    pub const ALL: [TestKind; 15] = [
+        TestKind::Frequency,
+        TestKind::BlockFrequency,
+        TestKind::CumulativeSums,
+        TestKind::Runs,
+        TestKind::LongestRun,
+        TestKind::Rank,
+        TestKind::Fft,
+        TestKind::NonOverlappingTemplate,
+        TestKind::OverlappingTemplate,
+        TestKind::Universal,
+        TestKind::ApproximateEntropy,
+        TestKind::RandomExcursions,
+        TestKind::RandomExcursionsVariant,
+        TestKind::Serial,
+        TestKind::LinearComplexity,
+    ];
+
+    /// Return name of the test, as used by the reference implementation.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TestKind::Frequency => "Frequency",
+            TestKind::BlockFrequency => "BlockFrequency",
+            TestKind::CumulativeSums => "CumulativeSums",
+            TestKind::Runs => "Runs",
+            TestKind::LongestRun => "LongestRun",
+            TestKind::Rank => "Rank",
+            TestKind::Fft => "FFT",
+            TestKind::NonOverlappingTemplate => "NonOverlappingTemplate",
+            TestKind::OverlappingTemplate => "OverlappingTemplate",
+            TestKind::Universal => "Universal",
+            TestKind::ApproximateEntropy => "ApproximateEntropy",
+            TestKind::RandomExcursions => "RandomExcursions",
+            TestKind::RandomExcursionsVariant => "RandomExcursionsVariant",
+            TestKind::Serial => "Serial",
+            TestKind::LinearComplexity => "LinearComplexity",
+        }
+    }
+}
+
+impl FromStr for TestKind {
+    type Err = Error;
+
+    /// Parse name of the test (case insensitive) or its number in the reference implementation (`1..=15`).
+    /// # Example
+    /// ```
+    /// use nistrs::battery::TestKind;
+    ///
+    /// assert_eq!("fft".parse(), Ok(TestKind::Fft));
+    /// assert_eq!("NonOverlappingTemplate".parse(), Ok(TestKind::NonOverlappingTemplate));
+    /// assert_eq!("3".parse(), Ok(TestKind::CumulativeSums));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let res = match s.parse::<usize>() {
+            Ok(v) => v.checked_sub(1).and_then(|x| TestKind::ALL.get(x)),
+            Err(_) => TestKind::ALL
+                .iter()
+                .find(|x| x.name().eq_ignore_ascii_case(s)),
+        };
+
+        res.copied().ok_or_else(|| Error::InvalidParameter {
+            name: "test",
+            allowed: TestKind::ALL
+                .iter()
+                .map(|x| x.name())
+                .collect::<Vec<_>>()
+                .join(", "),
+        })
+    }
+}
+
+/// Configuration of the battery.
+///
+/// Default values of parameters are the same as in the reference implementation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatteryConfig {
+    /// Significance level.
+    pub alpha: f64,
+    /// Tests to run.
+    pub tests: Vec<TestKind>,
+    /// Block length for [`block_frequency_test`].
+    pub block_frequency_m: usize,
+    /// Number of rows of every matrix for [`rank_test_with`].
+    pub rank_m: usize,
+    /// Number of columns of every matrix for [`rank_test_with`].
+    pub rank_q: usize,
+    /// Template length for [`non_overlapping_template_test`].
+    pub non_overlapping_template_m: usize,
+    /// Number of blocks for [`non_overlapping_template_test_with`].
+    pub non_overlapping_template_n_blocks: usize,
+    /// Template length for [`overlapping_template_test`].
+    pub overlapping_template_m: usize,
+    /// Method of computation of class probabilities for [`overlapping_template_test`].
+    pub overlapping_template_pi: PiMode,
+    /// Block length for [`approximate_entropy_test`].
+    pub approximate_entropy_m: usize,
+    /// Block length for [`serial_test`].
+    pub serial_m: usize,
+    /// Block length for [`linear_complexity_test`].
+    pub linear_complexity_m: usize,
+}
+
+impl Default for BatteryConfig {
+    fn default() -> Self {
+        BatteryConfig {
+            alpha: TEST_THRESHOLD,
+            tests: TestKind::ALL.to_vec(),
+            block_frequency_m: 128,
+            rank_m: 32,
+            rank_q: 32,
+            non_overlapping_template_m: 9,
+            non_overlapping_template_n_blocks: 8,
+            overlapping_template_m: 9,
+            overlapping_template_pi: PiMode::Approximation,
+            approximate_entropy_m: 10,
+            serial_m: 16,
+            linear_complexity_m: 500,
+        }
+    }
+}
+
+/// Result of one test of the battery.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestOutcome {
+    /// The test.
+    pub test: TestKind,
+    /// Reports of every sub-test (e.g. every template or state) or error if the test could not be run.
+    pub result: Result<Vec<TestReport>, Error>,
+}
+
+/// Aggregated result of the battery.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatteryReport {
+    /// Significance level.
+    pub alpha: f64,
+    /// Results of tests in order of [`BatteryConfig::tests`].
+    pub outcomes: Vec<TestOutcome>,
+}
+
+impl BatteryReport {
+    /// Return result of `test`, if it has been run.
+    pub fn get(&self, test: TestKind) -> Option<&TestOutcome> {
+        self.outcomes.iter().find(|x| x.test == test)
+    }
+
+    /// Return the iterator over reports of all sub-tests.
+    pub fn reports(&self) -> impl Iterator<Item = (TestKind, &TestReport)> {
+        self.outcomes.iter().flat_map(|x| {
+            x.result
+                .iter()
+                .flat_map(move |reports| reports.iter().map(move |report| (x.test, report)))
+        })
+    }
+
+    /// Return the iterator over reports of sub-tests which are not passed at significance level `alpha`.
+    pub fn failures(&self) -> impl Iterator<Item = (TestKind, &TestReport)> {
+        self.reports().filter(|x| !x.1.passed_at(self.alpha))
+    }
+
+    /// Return the iterator over tests which could not be run.
+    pub fn errors(&self) -> impl Iterator<Item = (TestKind, &Error)> {
+        self.outcomes
+            .iter()
+            .filter_map(|x| x.result.as_ref().err().map(|e| (x.test, e)))
+    }
+
+    /// Check that all sub-tests, which have been run, are passed and no test failed with an error.
+    ///
+    /// [`Error::NotApplicable`] of the random excursion tests is not an error of the input: such tests are
+    /// skipped, as in the reference implementation.
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+            && self
+                .errors()
+                .all(|x| matches!(x.1, Error::NotApplicable { .. }))
+    }
+}
+
+/// Runner of all NIST tests.
+/// # Example
+/// ```
+/// use nistrs::battery::{Battery, BatteryConfig, TestKind};
+/// use nistrs::BitsData;
+///
+/// let data = BitsData::from_binary((0..2048_u32).map(|x| (x * 37 % 251) as u8).collect());
+/// let battery = Battery::new(BatteryConfig {
+///     tests: vec![TestKind::Frequency, TestKind::Runs],
+///     ..Default::default()
+/// })
+/// .unwrap();
+///
+/// let report = battery.run(&data);
+/// assert_eq!(report.reports().count(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Battery {
+    config: BatteryConfig,
+}
+
+impl Battery {
+    /// Create battery with `config`.
+    /// # Errors
+    /// [`Error::InvalidParameter`] if significance level is not in `(0, 1)`.
+    pub fn new(config: BatteryConfig) -> Result<Self, Error> {
+        if !(config.alpha > 0_f64 && config.alpha < 1_f64) {
+            return Err(Error::InvalidParameter {
+                name: "alpha",
+                allowed: "(0, 1)".to_string(),
+            });
+        }
+
+        Ok(Battery { config })
+    }
+
+    /// Return configuration of the battery.
+    #[inline]
+    pub fn config(&self) -> &BatteryConfig {
+        &self.config
+    }
+
+    /// Run all enabled tests in parallel.
+    pub fn run<'a>(&self, data: impl Into<BitsView<'a>>) -> BatteryReport {
+        let data = data.into();
+        let outcomes = self
+            .config
+            .tests
+            .par_iter()
+            .map(|test| TestOutcome {
+                test: *test,
+                result: self.run_test(*test, data),
+            })
+            .collect();
+
+        BatteryReport {
+            alpha: self.config.alpha,
+            outcomes,
+        }
+    }
+
+    fn run_test(&self, test: TestKind, data: BitsView) -> Result<Vec<TestReport>, Error> {
+        let config = &self.config;
+
+        let res = match test {
+            TestKind::Frequency => vec![frequency_test_report(data)?],
+            TestKind::BlockFrequency => {
+                vec![block_frequency_test_report(data, config.block_frequency_m)?]
+            }
+            TestKind::CumulativeSums => cumulative_sums_test_report(data)?.to_vec(),
+            TestKind::Runs => vec![runs_test_report(data)?],
+            TestKind::LongestRun => vec![longest_run_of_ones_test_report(data)?],
+            TestKind::Rank => vec![rank_test_report_with(data, config.rank_m, config.rank_q)?],
+            TestKind::Fft => vec![fft_test_report(data)?],
+            TestKind::NonOverlappingTemplate => non_overlapping_template_test_report_with(
+                data,
+                crate::non_overlapping_template::standard_templates(
+                    config.non_overlapping_template_m,
+                )?,
+                config.non_overlapping_template_n_blocks,
+            )?
+            .into_iter()
+            .map(|x| x.1)
+            .collect(),
+            TestKind::OverlappingTemplate => vec![overlapping_template_test_report_with(
+                data,
+                crate::overlapping_template::ones(config.overlapping_template_m)?,
+                crate::overlapping_template::BLOCK_SIZE,
+                crate::overlapping_template::K,
+                config.overlapping_template_pi,
+            )?],
+            TestKind::Universal => vec![universal_test_report(data)?],
+            TestKind::ApproximateEntropy => vec![approximate_entropy_test_report(
+                data,
+                config.approximate_entropy_m,
+            )?],
+            TestKind::RandomExcursions => random_excursions_test_report(data)?.to_vec(),
+            TestKind::RandomExcursionsVariant => {
+                random_excursions_variant_test_report(data)?.to_vec()
+            }
+            TestKind::Serial => serial_test_report(data, config.serial_m)?.to_vec(),
+            TestKind::LinearComplexity => vec![linear_complexity_test_report(
+                data,
+                config.linear_complexity_m,
+            )?],
+        };
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,748 @@
+use std::io::{self, BufReader, Read};
+use std::ops::Index;
+
+use crate::Error;
+
+type WordT = u64;
+type BitsT = Vec<WordT>;
+
+const WORD_BITS: usize = WordT::BITS as usize;
+
+/// Structure contained sequence of bit.
+///
+/// Bits are packed into 64-bit words, the first bit of the sequence is the most significant bit of the
+/// first word. Unused bits of the last word are always `0`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitsData {
+    ones: usize,
+    len: usize,
+    data: BitsT,
+}
+
+impl BitsData {
+    /// Transform byte-vector into a `BitsData`.
+    /// # Example
+    ///
+    /// ```
+    /// use nistrs::BitsData;
+    ///
+    /// let result = BitsData::from_binary(vec![0x12, 0x21]);
+    /// ```
+    pub fn from_binary(data: Vec<u8>) -> Self {
+        let mut res = BitsData {
+            ones: 0,
+            len: data.len() * (u8::BITS as usize),
+            data: BitsT::with_capacity(data.len().div_ceil(WORD_BITS / 8)),
+        };
+
+        for chunk in data.chunks(WORD_BITS / 8) {
+            let mut word = [0_u8; WORD_BITS / 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+
+            let word = WordT::from_be_bytes(word);
+            res.ones += word.count_ones() as usize;
+            res.data.push(word);
+        }
+
+        res
+    }
+
+    /// Read bytes from `reader` into a `BitsData` without buffering the whole input.
+    ///
+    /// The first `offset` bytes of input are skipped. If `limit` is set, no more than `limit` bits are read,
+    /// otherwise `reader` is read to the end. Every byte is unpacked in the same way as in [`BitsData::from_binary`].
+    /// # Errors
+    /// Any error of `reader` except [`io::ErrorKind::Interrupted`], or [`io::ErrorKind::UnexpectedEof`] if the
+    /// input is shorter than `offset`.
+    /// # Example
+    ///
+    /// ```
+    /// use nistrs::BitsData;
+    ///
+    /// let input: &[u8] = &[0x00, 0x12, 0x21];
+    /// let result = BitsData::from_reader(input, 1, Some(12)).unwrap();
+    /// assert_eq!(result.len(), 12);
+    /// ```
+    pub fn from_reader<R: Read>(
+        mut reader: R,
+        offset: u64,
+        limit: Option<usize>,
+    ) -> io::Result<Self> {
+        skip(&mut reader, offset)?;
+
+        let n_bytes = match limit {
+            Some(v) => v.div_ceil(u8::BITS as usize) as u64,
+            None => u64::MAX,
+        };
+
+        let mut res = BitsData::default();
+        for_each_chunk(reader.take(n_bytes), |chunk| {
+            chunk.iter().for_each(|x| res.push_byte(*x));
+            Ok(true)
+        })?;
+
+        if let Some(v) = limit {
+            res.truncate(v);
+        }
+
+        Ok(res)
+    }
+
+    /// Read bits from `reader` in the binary mode of the reference implementation.
+    ///
+    /// Every one of `streams` sequences of `stream_len` bits is read by 4-byte chunks, the most significant bit
+    /// of every byte first; the rest of the last chunk of a sequence is discarded, so every sequence starts at
+    /// a 32-bit boundary. The first `offset` bytes of input are skipped. The sequences are concatenated.
+    /// # Errors
+    /// Any error of `reader` except [`io::ErrorKind::Interrupted`], or [`io::ErrorKind::UnexpectedEof`] if the
+    /// input is too short.
+    /// # Example
+    ///
+    /// ```
+    /// use nistrs::BitsData;
+    ///
+    /// let input: &[u8] = &[0xFF, 0x00, 0x00, 0x00, 0x0F, 0xFF, 0xFF, 0xFF];
+    /// let result = BitsData::from_sts_binary(input, 0, 4, 2).unwrap();
+    /// assert_eq!(result, BitsData::from_text("11110000".to_string()));
+    /// ```
+    pub fn from_sts_binary<R: Read>(
+        mut reader: R,
+        offset: u64,
+        stream_len: usize,
+        streams: usize,
+    ) -> io::Result<Self> {
+        skip(&mut reader, offset)?;
+
+        let mut reader = BufReader::new(reader);
+        let mut res = BitsData::default();
+        let mut chunk = [0_u8; 4];
+        for _ in 0..streams {
+            let mut read = 0;
+            while read < stream_len {
+                reader.read_exact(&mut chunk)?;
+
+                let bits = u32::from_be_bytes(chunk);
+                let count = (stream_len - read).min(u32::BITS as usize);
+                (0..count).for_each(|i| res.push((bits >> (u32::BITS as usize - 1 - i)) & 1 == 1));
+                read += count;
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Transform ASCII text consisting of `0` and `1` into a `BitsData`. Whitespace (including line
+    /// breaks) is skipped.
+    /// # Errors
+    /// [`Error::InvalidCharacter`] with the offset of the first character which is not `0`, `1` or whitespace.
+    /// # Example
+    ///
+    /// ```
+    /// use nistrs::{BitsData, Error};
+    ///
+    /// let result = BitsData::from_ascii("0010 0100\n0101 0001\n").unwrap();
+    /// assert_eq!(result.len(), 16);
+    ///
+    /// let result = BitsData::from_ascii("0010 0120");
+    /// assert_eq!(result, Err(Error::InvalidCharacter { offset: 7, byte: b'2' }));
+    /// ```
+    pub fn from_ascii<T: AsRef<[u8]>>(data: T) -> Result<Self, Error> {
+        let data = data.as_ref();
+        let mut res = BitsData {
+            ones: 0,
+            len: 0,
+            data: BitsT::with_capacity(data.len().div_ceil(WORD_BITS)),
+        };
+
+        for (i, x) in data.iter().enumerate() {
+            res.push_ascii(*x, i as u64)?;
+        }
+
+        Ok(res)
+    }
+
+    /// Read ASCII text consisting of `0` and `1` from `reader` into a `BitsData` without buffering the whole
+    /// input, as [`BitsData::from_ascii`] does.
+    ///
+    /// The first `offset` bytes of input are skipped. If `limit` is set, no more than `limit` bits are read,
+    /// otherwise `reader` is read to the end.
+    /// # Errors
+    /// Any error of `reader` except [`io::ErrorKind::Interrupted`], [`io::ErrorKind::UnexpectedEof`] if the
+    /// input is shorter than `offset`, or [`io::ErrorKind::InvalidData`] with [`Error::InvalidCharacter`] as
+    /// the inner error. The offset of the character is counted from the beginning of input, including the
+    /// skipped bytes.
+    /// # Example
+    ///
+    /// ```
+    /// use nistrs::{BitsData, Error};
+    ///
+    /// let input: &[u8] = b"xx01 1\n0110";
+    /// let result = BitsData::from_ascii_reader(input, 2, Some(6)).unwrap();
+    /// assert_eq!(result, BitsData::from_text("011011".to_string()));
+    ///
+    /// let err = BitsData::from_ascii_reader(input, 0, None).unwrap_err();
+    /// assert_eq!(
+    ///     err.get_ref().unwrap().downcast_ref::<Error>(),
+    ///     Some(&Error::InvalidCharacter { offset: 0, byte: b'x' })
+    /// );
+    /// ```
+    pub fn from_ascii_reader<R: Read>(
+        mut reader: R,
+        offset: u64,
+        limit: Option<usize>,
+    ) -> io::Result<Self> {
+        skip(&mut reader, offset)?;
+
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut res = BitsData::default();
+        let mut pos = offset;
+        for_each_chunk(reader, |chunk| {
+            for x in chunk {
+                if res.len == limit {
+                    return Ok(false);
+                }
+
+                res.push_ascii(*x, pos)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                pos += 1;
+            }
+
+            Ok(true)
+        })?;
+
+        Ok(res)
+    }
+
+    /// Transform a string consisting of `0` and `1` into a `BitsData`.
+    /// # Panic
+    /// Symbol not `0` or `1`. See [`BitsData::try_from_text`] for the non-panicking version.
+    /// # Example
+    ///
+    /// ```
+    /// use nistrs::BitsData;
+    ///
+    /// let result = BitsData::from_text("0010010001010001".to_string());
+    /// ```
+    pub fn from_text(data: String) -> Self {
+        BitsData::try_from_text(&data).unwrap_or_else(|_| panic!("incorrect text data!"))
+    }
+
+    /// Transform a string consisting of `0` and `1` into a `BitsData`.
+    ///
+    /// Accepts the same input as [`BitsData::from_text`]: every line may be surrounded by whitespace. Use
+    /// [`BitsData::from_ascii`] to skip whitespace inside lines as well.
+    /// # Errors
+    /// [`Error::InvalidCharacter`] with the offset of the first invalid character.
+    /// # Example
+    ///
+    /// ```
+    /// use nistrs::{BitsData, Error};
+    ///
+    /// let result = BitsData::try_from_text("  0010\n010001010001\n").unwrap();
+    /// assert_eq!(result.len(), 16);
+    ///
+    /// let result = BitsData::try_from_text("0010\n01 01");
+    /// assert_eq!(result, Err(Error::InvalidCharacter { offset: 7, byte: b' ' }));
+    /// ```
+    pub fn try_from_text(data: &str) -> Result<Self, Error> {
+        let mut res = BitsData {
+            ones: 0,
+            len: 0,
+            data: BitsT::with_capacity(data.len().div_ceil(WORD_BITS)),
+        };
+
+        let mut offset = 0;
+        for line in data.split_inclusive('\n') {
+            let begin = offset + (line.len() - line.trim_start().len());
+            for (i, x) in line.trim().char_indices() {
+                match x {
+                    '0' | '1' => res.push(x == '1'),
+                    _ => {
+                        return Err(Error::InvalidCharacter {
+                            offset: (begin + i) as u64,
+                            byte: line.as_bytes()[begin - offset + i],
+                        })
+                    }
+                }
+            }
+
+            offset += line.len();
+        }
+
+        Ok(res)
+    }
+
+    /// Append one bit to the end of sequence.
+    #[inline]
+    pub(crate) fn push(&mut self, bit: bool) {
+        let offset = self.len % WORD_BITS;
+        if offset == 0 {
+            self.data.push(0);
+        }
+
+        if bit {
+            *self.data.last_mut().unwrap() |= 1 << (WORD_BITS - 1 - offset);
+            self.ones += 1;
+        }
+
+        self.len += 1;
+    }
+
+    /// Append bit of ASCII character `byte` at `offset` of input, skipping whitespace.
+    #[inline]
+    fn push_ascii(&mut self, byte: u8, offset: u64) -> Result<(), Error> {
+        match byte {
+            b'0' | b'1' => self.push(byte == b'1'),
+            x if x.is_ascii_whitespace() => {}
+            _ => return Err(Error::InvalidCharacter { offset, byte }),
+        }
+
+        Ok(())
+    }
+
+    /// Append eight bits of `byte` to the end of sequence, the most significant bit first.
+    #[inline]
+    fn push_byte(&mut self, byte: u8) {
+        let offset = self.len % WORD_BITS;
+        if offset == 0 {
+            self.data.push(0);
+        }
+
+        if offset + (u8::BITS as usize) <= WORD_BITS {
+            *self.data.last_mut().unwrap() |= (byte as WordT) << (WORD_BITS - 8 - offset);
+            self.ones += byte.count_ones() as usize;
+            self.len += u8::BITS as usize;
+        } else {
+            for n in (0..u8::BITS).rev() {
+                self.push((byte >> n) & 1 == 1);
+            }
+        }
+    }
+
+    /// Shorten the sequence, keeping the first `len` bits.
+    /// Has no effect if `len` is greater than or equal to current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        self.data.truncate(len.div_ceil(WORD_BITS));
+
+        let offset = len % WORD_BITS;
+        if offset != 0 {
+            *self.data.last_mut().unwrap() &= !(WordT::MAX >> offset);
+        }
+
+        self.len = len;
+        self.ones = self.data.iter().map(|x| x.count_ones() as usize).sum();
+    }
+
+    /// Return number of bits in storage.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check that storage of bits is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the iterator for bit sequence.
+    #[inline]
+    pub fn iter(&self) -> BitsIter<'_> {
+        BitsIter::new(self, 0, self.len)
+    }
+
+    /// Return number of `1` in sequence.
+    #[inline]
+    pub fn ones(&self) -> usize {
+        self.ones
+    }
+
+    /// Return bit with index `i` or `None` if index is out of range.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<bool> {
+        if i < self.len {
+            Some(self.bit(i))
+        } else {
+            None
+        }
+    }
+
+    /// Return `count` bits started from `begin` as number, the first bit is the most significant.
+    /// # Panic
+    /// `count` is greater than 64 or range is out of sequence.
+    /// # Example
+    ///
+    /// ```
+    /// use nistrs::BitsData;
+    ///
+    /// let data = BitsData::from_text("0010110".to_string());
+    /// assert_eq!(data.bits(2, 4), 0b1011);
+    /// ```
+    #[inline]
+    pub fn bits(&self, begin: usize, count: usize) -> u64 {
+        assert!(
+            count <= WORD_BITS,
+            "no more than {} bits can be read",
+            WORD_BITS
+        );
+        assert!(begin + count <= self.len, "range is out of sequence");
+
+        if count == 0 {
+            return 0;
+        }
+
+        let index = begin / WORD_BITS;
+        let offset = begin % WORD_BITS;
+
+        let mut res = self.data[index] << offset;
+        if offset + count > WORD_BITS {
+            res |= self.data[index + 1] >> (WORD_BITS - offset);
+        }
+
+        res >> (WORD_BITS - count)
+    }
+
+    /// Append `count` least significant bits of `word` to the end of sequence, the most significant bit first.
+    #[inline]
+    pub(crate) fn push_bits(&mut self, word: u64, count: usize) {
+        if count == 0 {
+            return;
+        }
+
+        let word = (word << (WORD_BITS - count)) & (WordT::MAX << (WORD_BITS - count));
+        let offset = self.len % WORD_BITS;
+        if offset == 0 {
+            self.data.push(word);
+        } else {
+            *self.data.last_mut().unwrap() |= word >> offset;
+            if offset + count > WORD_BITS {
+                self.data.push(word << (WORD_BITS - offset));
+            }
+        }
+
+        self.ones += word.count_ones() as usize;
+        self.len += count;
+    }
+
+    /// Return packed representation of sequence.
+    /// The first bit is the most significant bit of the first word, unused bits of the last word are `0`.
+    #[inline]
+    pub fn words(&self) -> &[u64] {
+        &self.data
+    }
+
+    #[inline]
+    pub(crate) fn bit(&self, i: usize) -> bool {
+        (self.data[i / WORD_BITS] >> (WORD_BITS - 1 - i % WORD_BITS)) & 1 == 1
+    }
+}
+
+/// Skip the first `offset` bytes of `reader`.
+fn skip<R: Read>(reader: &mut R, offset: u64) -> io::Result<()> {
+    if io::copy(&mut reader.by_ref().take(offset), &mut io::sink())? < offset {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "offset is out of input",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Pass input of `reader` to `f` by chunks, until the end of input or until `f` returns `false`.
+fn for_each_chunk<R, F>(mut reader: R, mut f: F) -> io::Result<()>
+where
+    R: Read,
+    F: FnMut(&[u8]) -> io::Result<bool>,
+{
+    let mut buf = [0_u8; 8192];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        if !f(&buf[..n])? {
+            return Ok(());
+        }
+    }
+}
+
+impl Index<usize> for BitsData {
+    type Output = bool;
+
+    #[inline]
+    fn index(&self, i: usize) -> &Self::Output {
+        assert!(
+            i < self.len,
+            "index out of bounds: the len is {} but the index is {}",
+            self.len,
+            i
+        );
+
+        if self.bit(i) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a BitsData {
+    type Item = bool;
+    type IntoIter = BitsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over bits of `BitsData`.
+#[derive(Clone, Debug)]
+pub struct BitsIter<'a> {
+    data: &'a BitsData,
+    begin: usize,
+    end: usize,
+}
+
+impl<'a> BitsIter<'a> {
+    #[inline]
+    pub(crate) fn new(data: &'a BitsData, begin: usize, end: usize) -> Self {
+        BitsIter { data, begin, end }
+    }
+}
+
+impl Iterator for BitsIter<'_> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.begin == self.end {
+            return None;
+        }
+
+        self.begin += 1;
+        Some(self.data.bit(self.begin - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.begin;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for BitsIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.begin == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(self.data.bit(self.end))
+    }
+}
+
+impl ExactSizeIterator for BitsIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BitsData, Error};
+    use std::io;
+
+    #[test]
+    #[rustfmt::skip]
+    fn from_binary() {
+        let result = BitsData::from_binary(vec![0x12, 0x21]);
+        assert!(result.iter().eq([
+            false, false, false, true,
+            false, false, true,  false,
+            false, false, true,  false,
+            false, false, false, true
+        ]));
+        assert_eq!(result.ones(), 4);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn from_text() {
+        let result = BitsData::from_text("0010010001010001".to_string());
+        assert!(result.iter().eq([
+            false, false, true,  false,
+            false, true,  false, false,
+            false, true,  false, true,
+            false, false, false, true
+        ]));
+        assert_eq!(result.ones(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    #[rustfmt::skip]
+    fn from_error_text() {
+        BitsData::from_text("0010020001010001".to_string());
+    }
+
+    #[test]
+    fn packed_words() {
+        let bytes: Vec<u8> = (0..20).map(|x| x * 13 + 7).collect();
+        let text: String = bytes.iter().map(|x| format!("{:08b}", x)).collect();
+
+        let binary = BitsData::from_binary(bytes);
+        let text = BitsData::from_text(text);
+
+        assert_eq!(binary, text);
+        assert_eq!(binary.len(), 160);
+        assert_eq!(binary.words().len(), 3);
+        assert_eq!(binary.words()[2] & 0xFFFF_FFFF, 0);
+    }
+
+    #[test]
+    fn from_reader() {
+        let bytes: Vec<u8> = (0..20000_u32).map(|x| (x * 7 + x / 3) as u8).collect();
+        let expected = BitsData::from_binary(bytes.clone());
+
+        assert_eq!(
+            BitsData::from_reader(bytes.as_slice(), 0, None).unwrap(),
+            expected
+        );
+
+        let result = BitsData::from_reader(bytes.as_slice(), 3, Some(1001)).unwrap();
+        assert_eq!(result.len(), 1001);
+        assert_eq!(result.ones(), result.iter().filter(|x| *x).count());
+        assert!(result.iter().eq(expected.iter().skip(24).take(1001)));
+
+        let result = BitsData::from_reader(bytes.as_slice(), 19999, Some(100)).unwrap();
+        assert_eq!(result.len(), 8);
+
+        assert_eq!(
+            BitsData::from_reader(bytes.as_slice(), 20001, None)
+                .unwrap_err()
+                .kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn from_ascii() {
+        let expected = BitsData::from_text("0110100111".to_string());
+
+        assert_eq!(BitsData::from_ascii("01101 00111"), Ok(expected.clone()));
+        assert_eq!(BitsData::from_ascii(b"\t0110\r\n1001\n11\n"), Ok(expected));
+        assert_eq!(
+            BitsData::from_ascii("0110,1"),
+            Err(Error::InvalidCharacter {
+                offset: 4,
+                byte: b','
+            })
+        );
+    }
+
+    #[test]
+    fn from_ascii_reader() {
+        let text: String = (0..20000_u32)
+            .map(|x| match x % 81 {
+                80 => '\n',
+                _ => char::from(b'0' + (x * 7 / 3 % 2) as u8),
+            })
+            .collect();
+        let expected = BitsData::from_ascii(&text).unwrap();
+
+        assert_eq!(
+            BitsData::from_ascii_reader(text.as_bytes(), 0, None).unwrap(),
+            expected
+        );
+
+        let result = BitsData::from_ascii_reader(text.as_bytes(), 81, Some(10000)).unwrap();
+        assert_eq!(result.len(), 10000);
+        assert!(result.iter().eq(expected.iter().skip(80).take(10000)));
+
+        let mut text = text.into_bytes();
+        text[9000] = b'a';
+        let err = BitsData::from_ascii_reader(text.as_slice(), 10, None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<Error>(),
+            Some(&Error::InvalidCharacter {
+                offset: 9000,
+                byte: b'a'
+            })
+        );
+    }
+
+    #[test]
+    fn from_sts_binary() {
+        let bytes: Vec<u8> = (0..1000_u32).map(|x| (x * 7 + x / 3) as u8).collect();
+        let all = BitsData::from_binary(bytes.clone());
+
+        let result = BitsData::from_sts_binary(bytes.as_slice(), 4, 100, 3).unwrap();
+        assert_eq!(result.len(), 300);
+        for i in 0..3 {
+            let begin = 32 + 128 * i;
+            assert!(result
+                .iter()
+                .skip(100 * i)
+                .take(100)
+                .eq(all.iter().skip(begin).take(100)));
+        }
+
+        assert_eq!(
+            BitsData::from_sts_binary(bytes.as_slice(), 0, 1000, 8)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn truncate() {
+        let mut data = BitsData::from_binary(vec![0xFF; 16]);
+
+        data.truncate(200);
+        assert_eq!(data.len(), 128);
+
+        data.truncate(70);
+        assert_eq!(data.len(), 70);
+        assert_eq!(data.ones(), 70);
+        assert_eq!(data.words(), &[u64::MAX, 0xFC00_0000_0000_0000]);
+    }
+
+    #[test]
+    fn push_bits() {
+        let data = BitsData::from_binary((0..64_u32).map(|x| (x * 7) as u8).collect());
+
+        for count in [1, 5, 63, 64] {
+            let mut result = BitsData::default();
+            for i in (0..data.len() - count).step_by(count) {
+                let garbage = u64::MAX.checked_shl(count as u32).unwrap_or(0);
+                result.push_bits(data.bits(i, count) | garbage, count);
+            }
+            assert_eq!(result.ones(), result.iter().filter(|x| *x).count());
+            assert!(result.iter().eq(data.iter().take(result.len())));
+        }
+    }
+
+    #[test]
+    fn bits() {
+        let data = BitsData::from_binary((0..16).collect());
+
+        assert_eq!(data.bits(0, 8), 0x00);
+        assert_eq!(data.bits(8, 16), 0x0102);
+        assert_eq!(data.bits(60, 8), 0x70);
+        assert_eq!(data.bits(56, 64), 0x0708_090A_0B0C_0D0E);
+        assert_eq!(data.bits(127, 1), 1);
+        assert_eq!(data.bits(5, 0), 0);
+
+        for i in 0..data.len() {
+            assert_eq!(data.bits(i, 1) == 1, data[i]);
+        }
+    }
+}
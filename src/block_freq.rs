@@ -1,5 +1,3 @@
-use statrs::function::gamma::gamma_ur;
-
 use super::*;
 
 /// Frequency Test within a Block.
@@ -8,35 +6,70 @@ use super::*;
 /// whether the frequency of ones in an M-bit block is approximately M/2, as would be expected under an
 /// assumption of randomness. For block size M=1, this test degenerates to test 1, the Frequency (Monobit) test.
 /// `M` must be > 0;
+/// # Errors
+/// [`Error::InvalidParameter`] if `m` is `0`, [`Error::TooFewBits`] if the sequence is shorter than `m` bits.
 /// # Example
 /// ```
 /// use nistrs::block_freq::block_frequency_test;
 /// use nistrs::BitsData;
-
+///
 /// let data = BitsData::from_text("11001001000011111101101010100010001000010110100011000010001101001100010011000110011000101000101110000000".to_string());
 /// assert_eq!(block_frequency_test(&data, 10).unwrap().1, 0.70643844964128211);
 /// ```
-pub fn block_frequency_test(data: &super::BitsData, m: usize) -> Result<TestResultT, String> {
-    let nbits = data.len();
-    if nbits < m {
-        return Err("Size of block must be lower number of bits".to_string());
+pub fn block_frequency_test<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<TestResultT, Error> {
+    block_frequency_test_report(data, m).map(|x| x.result())
+}
+
+/// Frequency Test within a Block with detailed result.
+///
+/// Parameters: `expected` - expected number of ones in every block, `M/2`. Statistics: `chi2`. Counts of ones of
+/// every block are not kept, so the size of report does not depend on the length of sequence.
+/// See [`block_frequency_test`].
+/// # Example
+/// ```
+/// use nistrs::block_freq::block_frequency_test_report;
+/// use nistrs::BitsData;
+///
+/// let data = BitsData::from_text("0110011010".to_string());
+/// let report = block_frequency_test_report(&data, 3).unwrap();
+/// assert_eq!(report.parameter("expected"), Some(1.5));
+/// assert!((report.statistic("chi2").unwrap() - 1_f64).abs() < 1e-12);
+/// assert!(report.observed.is_empty());
+/// ```
+pub fn block_frequency_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<TestReport, Error> {
+    let data = data.into();
+    if m == 0 {
+        return Err(Error::InvalidParameter {
+            name: "m",
+            allowed: "1..".to_string(),
+        });
     }
+    check_len(&data, m)?;
+
+    let nbits = data.len();
 
     let n_blocks = nbits / m;
 
     let mut sum = f64::default();
-    for i in 0..n_blocks {
-        let mut block_sum = 0_usize;
-        for j in 0..m {
-            block_sum += data[i * m + j] as usize;
-        }
-
+    for block in data.chunks_exact(m) {
+        let block_sum = block.ones();
         let v = (block_sum as f64) / (m as f64) - 0.5;
         sum += v.powf(2_f64);
     }
 
     let chi_squared = 4_f64 * (m as f64) * sum;
-    let p = gamma_ur((n_blocks as f64) / 2_f64, chi_squared / 2_f64);
+    let p = igamc((n_blocks as f64) / 2_f64, chi_squared / 2_f64);
 
-    Ok((p >= TEST_THRESHOLD, p))
+    Ok(TestReport::new("BlockFrequency", p)
+        .with_parameter("m", m as f64)
+        .with_parameter("n_blocks", n_blocks as f64)
+        .with_parameter("expected", (m as f64) / 2_f64)
+        .with_statistic("chi2", chi_squared)
+        .with_degrees_of_freedom(n_blocks as f64))
 }
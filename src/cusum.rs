@@ -13,33 +13,143 @@ use super::*;
 /// zero. For certain types of non-random sequences, the excursions of this random walk from zero will be
 /// large.
 /// Return `P-values` for cusum-forward and cusum-reverse.
-pub fn cumulative_sums_test(data: &BitsData) -> [TestResultT; 2] {
-    let mut s = isize::default();
-    let mut sup = isize::default();
-    let mut inf = isize::default();
-    let mut z = isize::default();
-    let mut zrev = isize::default();
+/// # Errors
+/// [`Error::TooFewBits`] if the sequence is empty.
+pub fn cumulative_sums_test<'a>(data: impl Into<BitsView<'a>>) -> Result<[TestResultT; 2], Error> {
+    cumulative_sums_test_report(data).map(|x| x.map(|x| x.result()))
+}
 
+/// Cumulative Sums (Cusum) Test with detailed result.
+///
+/// Statistics: `z` - the largest excursion of the random walk.
+/// See [`cumulative_sums_test`].
+pub fn cumulative_sums_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
+) -> Result<[TestReport; 2], Error> {
+    let data = data.into();
+    check_len(&data, 1)?;
+
+    let mut walk = RandomWalk::default();
     for el in data.iter() {
-        if *el {
-            s += 1;
-        } else {
-            s -= 1;
+        walk.push(el);
+    }
+
+    Ok(cusum_reports(data.len(), &walk))
+}
+
+/// Cumulative Sums (Cusum) Test over a stream of unbounded length.
+///
+/// Chunks of the sequence are passed to [`update`](Self::update) or [`update_bits`](Self::update_bits), only
+/// the current value and extremes of the random walk are kept. Results are equal to ones of
+/// [`cumulative_sums_test`] on the whole sequence.
+/// # Example
+/// ```
+/// use nistrs::cusum::{cumulative_sums_test, CumulativeSumsAccumulator};
+/// use nistrs::BitsData;
+///
+/// let mut acc = CumulativeSumsAccumulator::new();
+/// acc.update(&[0x23, 0x44]);
+/// acc.update(&[0x93]);
+///
+/// let data = BitsData::from_binary(vec![0x23, 0x44, 0x93]);
+/// assert_eq!(acc.finalize(), cumulative_sums_test(&data));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CumulativeSumsAccumulator {
+    n_bits: usize,
+    walk: RandomWalk,
+}
+
+impl CumulativeSumsAccumulator {
+    /// Create accumulator of empty sequence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append bytes to the sequence, the most significant bit of every byte first.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for x in bytes {
+            for i in (0..8).rev() {
+                self.walk.push((x >> i) & 1 == 1);
+            }
+        }
+        self.n_bits += bytes.len() * 8;
+    }
+
+    /// Append bits to the sequence.
+    pub fn update_bits<'a>(&mut self, data: impl Into<BitsView<'a>>) {
+        let data = data.into();
+        for el in data.iter() {
+            self.walk.push(el);
+        }
+        self.n_bits += data.len();
+    }
+
+    /// Return number of bits passed so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.n_bits
+    }
+
+    /// Check that no bits were passed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n_bits == 0
+    }
+
+    /// Return results of the test on bits passed so far, see [`cumulative_sums_test`].
+    /// # Errors
+    /// [`Error::TooFewBits`] if the sequence is empty.
+    pub fn finalize(&self) -> Result<[TestResultT; 2], Error> {
+        self.finalize_report().map(|x| x.map(|x| x.result()))
+    }
+
+    /// Return detailed results of the test on bits passed so far, see [`cumulative_sums_test_report`].
+    /// # Errors
+    /// [`Error::TooFewBits`] if the sequence is empty.
+    pub fn finalize_report(&self) -> Result<[TestReport; 2], Error> {
+        if self.n_bits == 0 {
+            return Err(Error::TooFewBits {
+                required: 1,
+                actual: 0,
+            });
         }
 
-        if s > sup {
-            sup += 1;
+        Ok(cusum_reports(self.n_bits, &self.walk))
+    }
+}
+
+/// State of the random walk of adjusted (-1, +1) digits: the current sum and its extremes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct RandomWalk {
+    s: isize,
+    sup: isize,
+    inf: isize,
+}
+
+impl RandomWalk {
+    #[inline]
+    fn push(&mut self, bit: bool) {
+        if bit {
+            self.s += 1;
+        } else {
+            self.s -= 1;
         }
 
-        if s < inf {
-            inf -= 1;
+        if self.s > self.sup {
+            self.sup += 1;
         }
 
-        z = max(sup, -inf);
-        zrev = max(sup - s, s - inf);
+        if self.s < self.inf {
+            self.inf -= 1;
+        }
     }
+}
+
+fn cusum_reports(n: usize, walk: &RandomWalk) -> [TestReport; 2] {
+    let z = max(walk.sup, -walk.inf);
+    let zrev = max(walk.sup - walk.s, walk.s - walk.inf);
 
-    let n = data.len();
     let sqrtn = (n as f64).sqrt();
 
     let mut begin = (-(n as isize) / z + 1) / 4;
@@ -76,7 +186,14 @@ pub fn cumulative_sums_test(data: &BitsData) -> [TestResultT; 2] {
 
     let p1 = 1_f64 - sum1 + sum2;
 
-    [(p0 >= TEST_THRESHOLD, p0), (p1 >= TEST_THRESHOLD, p1)]
+    let report = |p: f64, mode: f64, z: isize| {
+        TestReport::new("CumulativeSums", p)
+            .with_parameter("n", n as f64)
+            .with_parameter("mode", mode)
+            .with_statistic("z", z as f64)
+    };
+
+    [report(p0, 0_f64, z), report(p1, 1_f64, zrev)]
 }
 
 fn normal(x: f64) -> f64 {
@@ -0,0 +1,374 @@
+use crate::{BitsData, Error};
+
+/// Order of bits in every byte (or word) of binary input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// The most significant bit first, as [`BitsData::from_binary`] does.
+    #[default]
+    MsbFirst,
+    /// The least significant bit first.
+    LsbFirst,
+}
+
+/// Byte order of words in binary input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WordOrder {
+    /// Every byte is a separate unit.
+    #[default]
+    Bytes,
+    /// Input is a sequence of little-endian 16-bit words.
+    Le16,
+    /// Input is a sequence of little-endian 32-bit words.
+    Le32,
+    /// Input is a sequence of little-endian 64-bit words.
+    Le64,
+}
+
+impl WordOrder {
+    /// Size of word in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            WordOrder::Bytes => 1,
+            WordOrder::Le16 => 2,
+            WordOrder::Le32 => 4,
+            WordOrder::Le64 => 8,
+        }
+    }
+}
+
+/// Layout of bits in binary input.
+///
+/// Input is split into words of [`WordOrder::size`] bytes, every word is read as a number in the given byte
+/// order, and bits of the number are taken in [`BitOrder`].
+/// # Example
+/// ```
+/// use nistrs::{BitLayout, BitOrder, BitsData, WordOrder};
+///
+/// let layout = BitLayout {
+///     bit_order: BitOrder::MsbFirst,
+///     word_order: WordOrder::Le16,
+/// };
+/// let data = BitsData::from_binary_with(vec![0x01, 0x80], layout).unwrap();
+/// assert_eq!(data, BitsData::from_text("1000000000000001".to_string()));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitLayout {
+    /// Order of bits.
+    pub bit_order: BitOrder,
+    /// Order of bytes.
+    pub word_order: WordOrder,
+}
+
+impl BitLayout {
+    /// The least significant bit of every byte first.
+    pub const LSB_FIRST: BitLayout = BitLayout {
+        bit_order: BitOrder::LsbFirst,
+        word_order: WordOrder::Bytes,
+    };
+
+    /// Reorder `data` in place, so that it can be unpacked the most significant bit of every byte first.
+    fn normalize(&self, data: &mut [u8]) -> Result<(), Error> {
+        let size = self.word_order.size();
+        if data.len() % size != 0 {
+            return Err(Error::InvalidParameter {
+                name: "data",
+                allowed: format!("whole number of {}-byte words", size),
+            });
+        }
+
+        match self.bit_order {
+            // Bits of the number from the most significant one: big-endian bytes, MSB first.
+            BitOrder::MsbFirst => data.chunks_mut(size).for_each(|x| x.reverse()),
+            // Bits of the number from the least significant one: little-endian bytes, LSB first.
+            BitOrder::LsbFirst => data.iter_mut().for_each(|x| *x = x.reverse_bits()),
+        }
+
+        Ok(())
+    }
+}
+
+impl BitsData {
+    /// Transform byte-vector into a `BitsData` with the given bit `layout`.
+    /// # Errors
+    /// [`Error::InvalidParameter`] if length of `data` is not a multiple of the word size.
+    /// # Example
+    /// ```
+    /// use nistrs::{BitLayout, BitsData};
+    ///
+    /// let data = BitsData::from_binary_with(vec![0x01, 0x03], BitLayout::LSB_FIRST).unwrap();
+    /// assert_eq!(data, BitsData::from_text("1000000011000000".to_string()));
+    /// ```
+    pub fn from_binary_with(mut data: Vec<u8>, layout: BitLayout) -> Result<Self, Error> {
+        if layout.word_order != WordOrder::Bytes || layout.bit_order != BitOrder::MsbFirst {
+            layout.normalize(&mut data)?;
+        }
+
+        Ok(BitsData::from_binary(data))
+    }
+
+    /// Transform hexadecimal text (e.g. a hex dump) into a `BitsData`, every pair of digits is a byte.
+    ///
+    /// Whitespace is skipped, digits may be in any case. Bytes are unpacked with the given bit `layout`.
+    /// # Errors
+    /// [`Error::InvalidCharacter`] with the offset of the first character which is not a hexadecimal digit or
+    /// whitespace, [`Error::InvalidParameter`] if the number of digits is odd or the number of bytes is not a
+    /// multiple of the word size.
+    /// # Example
+    /// ```
+    /// use nistrs::{BitLayout, BitsData};
+    ///
+    /// let data = BitsData::from_hex("C9 0f\n", BitLayout::default()).unwrap();
+    /// assert_eq!(data, BitsData::from_text("1100100100001111".to_string()));
+    /// ```
+    pub fn from_hex(text: &str, layout: BitLayout) -> Result<Self, Error> {
+        let mut bytes = Vec::with_capacity(text.len() / 2);
+        let mut high = None;
+        for (i, x) in text.bytes().enumerate() {
+            let digit = match x {
+                b'0'..=b'9' => x - b'0',
+                b'a'..=b'f' => x - b'a' + 10,
+                b'A'..=b'F' => x - b'A' + 10,
+                _ if x.is_ascii_whitespace() => continue,
+                _ => {
+                    return Err(Error::InvalidCharacter {
+                        offset: i as u64,
+                        byte: x,
+                    })
+                }
+            };
+
+            match high.take() {
+                Some(v) => bytes.push((v << 4) | digit),
+                None => high = Some(digit),
+            }
+        }
+
+        if high.is_some() {
+            return Err(Error::InvalidParameter {
+                name: "text",
+                allowed: "even number of hexadecimal digits".to_string(),
+            });
+        }
+
+        BitsData::from_binary_with(bytes, layout)
+    }
+
+    /// Transform Base64 text into a `BitsData`.
+    ///
+    /// Both the standard and the URL-safe alphabets are accepted, padding is optional, whitespace is skipped.
+    /// Only canonical encoding is accepted: if there is padding, it is complete, and unused bits of the last
+    /// digit are zero. Bytes are unpacked with the given bit `layout`.
+    /// # Errors
+    /// [`Error::InvalidCharacter`] with the offset of the first character which is not in the alphabet, follows
+    /// the padding or is the last digit with non-zero unused bits, [`Error::InvalidParameter`] if the text or its
+    /// padding is truncated or the number of bytes is not a multiple of the word size.
+    /// # Example
+    /// ```
+    /// use nistrs::{BitLayout, BitsData};
+    ///
+    /// let data = BitsData::from_base64("yQ8=", BitLayout::default()).unwrap();
+    /// assert_eq!(data, BitsData::from_text("1100100100001111".to_string()));
+    /// ```
+    pub fn from_base64(text: &str, layout: BitLayout) -> Result<Self, Error> {
+        let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+        let mut acc = 0_u32;
+        let mut n_digits = 0_usize;
+        let mut n_padding = 0_usize;
+        let mut last = 0_usize;
+        for (i, x) in text.bytes().enumerate() {
+            let padding = n_padding != 0;
+            let digit = match x {
+                b'A'..=b'Z' if !padding => x - b'A',
+                b'a'..=b'z' if !padding => x - b'a' + 26,
+                b'0'..=b'9' if !padding => x - b'0' + 52,
+                b'+' | b'-' if !padding => 62,
+                b'/' | b'_' if !padding => 63,
+                b'=' if n_digits % 4 >= 2 && n_digits % 4 + n_padding < 4 => {
+                    n_padding += 1;
+                    continue;
+                }
+                _ if x.is_ascii_whitespace() => continue,
+                _ => {
+                    return Err(Error::InvalidCharacter {
+                        offset: i as u64,
+                        byte: x,
+                    })
+                }
+            };
+
+            acc = (acc << 6) | digit as u32;
+            n_digits += 1;
+            last = i;
+            if n_digits % 4 == 0 {
+                bytes.extend_from_slice(&acc.to_be_bytes()[1..]);
+                acc = 0;
+            }
+        }
+
+        match n_digits % 4 {
+            0 => {}
+            1 => {
+                return Err(Error::InvalidParameter {
+                    name: "text",
+                    allowed: "complete base64 data".to_string(),
+                })
+            }
+            r => {
+                if n_padding != 0 && r + n_padding != 4 {
+                    return Err(Error::InvalidParameter {
+                        name: "text",
+                        allowed: "complete base64 padding".to_string(),
+                    });
+                }
+
+                // 4 or 2 bits of the last digit are not used.
+                if acc & ((1 << (2 * (4 - r))) - 1) != 0 {
+                    return Err(Error::InvalidCharacter {
+                        offset: last as u64,
+                        byte: text.as_bytes()[last],
+                    });
+                }
+
+                let bits = 6 * r as u32;
+                let tail = (acc << (32 - bits)).to_be_bytes();
+                bytes.extend_from_slice(&tail[..r - 1]);
+            }
+        }
+
+        BitsData::from_binary_with(bytes, layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        let bytes = vec![0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+        let word = 0xf0debc9a78563412_u64;
+
+        let layout = |bit_order, word_order| BitLayout {
+            bit_order,
+            word_order,
+        };
+
+        let data =
+            BitsData::from_binary_with(bytes.clone(), layout(BitOrder::MsbFirst, WordOrder::Le64))
+                .unwrap();
+        assert_eq!(data.words(), [word]);
+
+        let data =
+            BitsData::from_binary_with(bytes.clone(), layout(BitOrder::LsbFirst, WordOrder::Le64))
+                .unwrap();
+        assert_eq!(data.words(), [word.reverse_bits()]);
+
+        let data =
+            BitsData::from_binary_with(bytes.clone(), layout(BitOrder::MsbFirst, WordOrder::Le32))
+                .unwrap();
+        assert_eq!(data.words(), [0x78563412f0debc9a]);
+
+        let data =
+            BitsData::from_binary_with(bytes.clone(), layout(BitOrder::LsbFirst, WordOrder::Le16))
+                .unwrap();
+        assert_eq!(
+            data.words(),
+            [u64::from_be_bytes(bytes.clone().try_into().unwrap())
+                .reverse_bits()
+                .swap_bytes()]
+        );
+
+        assert_eq!(
+            BitsData::from_binary_with(
+                bytes[..6].to_vec(),
+                layout(BitOrder::LsbFirst, WordOrder::Le32)
+            ),
+            Err(Error::InvalidParameter {
+                name: "data",
+                allowed: "whole number of 4-byte words".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn hex() {
+        let expected = BitsData::from_binary(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(
+            BitsData::from_hex("DEad\tbe ef\r\n", BitLayout::default()),
+            Ok(expected)
+        );
+        assert_eq!(
+            BitsData::from_hex("de ad bg", BitLayout::default()),
+            Err(Error::InvalidCharacter {
+                offset: 7,
+                byte: b'g'
+            })
+        );
+        assert!(BitsData::from_hex("dea", BitLayout::default()).is_err());
+    }
+
+    #[test]
+    fn base64() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let text = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4vMDEyMzQ1Njc4OTo7PD0+\n\
+                    P0BBQkNERUZHSElKS0xNTk9QUVJTVFVWV1hZWltcXV5fYGFiY2RlZmdoaWprbG1ub3BxcnN0dXZ3eHl6e3x9fn+A\n\
+                    gYKDhIWGh4iJiouMjY6PkJGSk5SVlpeYmZqbnJ2en6ChoqOkpaanqKmqq6ytrq+wsbKztLW2t7i5uru8vb6/wMHC\n\
+                    w8TFxsfIycrLzM3Oz9DR0tPU1dbX2Nna29zd3t/g4eLj5OXm5+jp6uvs7e7v8PHy8/T19vf4+fr7/P3+/w==";
+        assert_eq!(
+            BitsData::from_base64(text, BitLayout::default()),
+            Ok(BitsData::from_binary(bytes.clone()))
+        );
+        assert_eq!(
+            BitsData::from_base64(
+                &text.replace('+', "-").replace('/', "_").replace('=', ""),
+                BitLayout::default()
+            ),
+            Ok(BitsData::from_binary(bytes))
+        );
+
+        assert_eq!(
+            BitsData::from_base64("yQ", BitLayout::default()),
+            Ok(BitsData::from_binary(vec![0xc9]))
+        );
+        assert_eq!(
+            BitsData::from_base64("yQ=8", BitLayout::default()),
+            Err(Error::InvalidCharacter {
+                offset: 3,
+                byte: b'8'
+            })
+        );
+        assert!(BitsData::from_base64("yQ8=y", BitLayout::default()).is_err());
+        assert!(BitsData::from_base64("=", BitLayout::default()).is_err());
+
+        // Non-canonical padding and unused bits.
+        assert_eq!(
+            BitsData::from_base64("yQ==", BitLayout::default()),
+            Ok(BitsData::from_binary(vec![0xc9]))
+        );
+        assert!(matches!(
+            BitsData::from_base64("yQ=", BitLayout::default()),
+            Err(Error::InvalidParameter { name: "text", .. })
+        ));
+        assert_eq!(
+            BitsData::from_base64("yQ8==", BitLayout::default()),
+            Err(Error::InvalidCharacter {
+                offset: 4,
+                byte: b'='
+            })
+        );
+        assert_eq!(
+            BitsData::from_base64("yR==", BitLayout::default()),
+            Err(Error::InvalidCharacter {
+                offset: 1,
+                byte: b'R'
+            })
+        );
+        assert_eq!(
+            BitsData::from_base64("yQ9", BitLayout::default()),
+            Err(Error::InvalidCharacter {
+                offset: 2,
+                byte: b'9'
+            })
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt;
+
+/// Error type for tests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "kind"))]
+pub enum Error {
+    /// The sequence is shorter than the test requires.
+    TooFewBits {
+        /// Minimal number of bits.
+        required: usize,
+        /// Number of bits in sequence.
+        actual: usize,
+    },
+    /// A parameter of the test is out of the allowed range.
+    InvalidParameter {
+        /// Name of parameter.
+        name: &'static str,
+        /// Allowed values of parameter.
+        allowed: String,
+    },
+    /// The test is not applicable: the random walk has an insufficient number of cycles.
+    NotApplicable {
+        /// Number of cycles in the random walk.
+        cycles: usize,
+        /// Minimal number of cycles.
+        required: usize,
+    },
+    /// The random walk has more cycles than expected.
+    TooManyCycles {
+        /// Maximal number of cycles.
+        max: usize,
+    },
+    /// The text contains a character which is not `0`, `1` or whitespace.
+    InvalidCharacter {
+        /// Offset of the character in bytes from the beginning of input.
+        offset: u64,
+        /// The character.
+        byte: u8,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TooFewBits { required, actual } => {
+                write!(f, "{} bit required! In storage: {}", required, actual)
+            }
+            Error::InvalidParameter { name, allowed } => {
+                write!(f, "invalid parameter `{}`, allowed: {}", name, allowed)
+            }
+            Error::NotApplicable { cycles, required } => write!(
+                f,
+                "test not applicable, there are an insufficient number of cycles: {} (required: {})",
+                cycles, required
+            ),
+            Error::TooManyCycles { max } => {
+                write!(f, "exceeding the max number of cycles expected: {}", max)
+            }
+            Error::InvalidCharacter { offset, byte } => {
+                write!(f, "invalid character {:?} at offset {}", *byte as char, offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
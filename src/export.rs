@@ -0,0 +1,358 @@
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use crate::assessment::AssessmentReport;
+use crate::battery::BatteryReport;
+
+#[cfg(feature = "serde")]
+use crate::{assessment::SubTestSummary, battery::TestKind, Error, TestReport};
+#[cfg(feature = "serde")]
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+impl BatteryReport {
+    /// Serialize the report into JSON.
+    ///
+    /// Every sub-test is an object with parameters, statistics, `P-value` and pass flag, every test which
+    /// could not be run is an object with the error.
+    /// # Example
+    /// ```
+    /// use nistrs::battery::{Battery, BatteryConfig, TestKind};
+    /// use nistrs::BitsData;
+    ///
+    /// let data = BitsData::from_text("1011010101".to_string());
+    /// let battery = Battery::new(BatteryConfig {
+    ///     tests: vec![TestKind::Frequency],
+    ///     ..Default::default()
+    /// })
+    /// .unwrap();
+    ///
+    /// let json = battery.run(&data).to_json().unwrap();
+    /// assert!(json.contains(r#""p_value":0.5270892568655381"#));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&BatteryRecord::from(self))
+    }
+
+    /// Write the report as CSV, one line per sub-test.
+    ///
+    /// Columns: `test`, `sub_test`, `p_value`, `passed`, `parameters`, `statistics`, `error`. Parameters and
+    /// statistics are written as `name=value` pairs separated by `;`.
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(
+            out,
+            "test,sub_test,p_value,passed,parameters,statistics,error"
+        )?;
+
+        for outcome in self.outcomes.iter() {
+            match &outcome.result {
+                Ok(reports) => {
+                    for (i, report) in reports.iter().enumerate() {
+                        writeln!(
+                            out,
+                            "{},{},{},{},{},{},",
+                            outcome.test.name(),
+                            i,
+                            report.p_value,
+                            report.passed_at(self.alpha),
+                            csv_pairs(&report.parameters),
+                            csv_pairs(&report.statistics),
+                        )?;
+                    }
+                }
+                Err(e) => {
+                    writeln!(
+                        out,
+                        "{},,,,,,{}",
+                        outcome.test.name(),
+                        csv_field(&e.to_string())
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl AssessmentReport {
+    /// Serialize the report into JSON.
+    ///
+    /// Contains summary of every sub-test and results of the battery for every sequence.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&AssessmentRecord::from(self))
+    }
+
+    /// Write summaries of sub-tests as CSV, one line per sub-test.
+    ///
+    /// Columns: `test`, `sub_test`, `c1`..`c10` - histogram of `P-values`, `p_value_t` - `P-value` of the
+    /// uniformity test, `uniformity_passed`, `passed`, `total`, `proportion`, `min_passed`, `max_passed`,
+    /// `proportion_passed`.
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(
+            out,
+            "test,sub_test,c1,c2,c3,c4,c5,c6,c7,c8,c9,c10,p_value_t,uniformity_passed,passed,total,\
+             proportion,min_passed,max_passed,proportion_passed"
+        )?;
+
+        for summary in self.summaries.iter() {
+            write!(out, "{},{}", summary.test.name(), summary.index)?;
+            for count in summary.histogram.iter() {
+                write!(out, ",{}", count)?;
+            }
+
+            writeln!(
+                out,
+                ",{},{},{},{},{},{},{},{}",
+                summary
+                    .uniformity
+                    .map(|x| x.to_string())
+                    .unwrap_or_default(),
+                summary.uniformity_passed(),
+                summary.passed,
+                summary.total(),
+                summary.proportion(),
+                summary.passed_range.0,
+                summary.passed_range.1,
+                summary.proportion_passed(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn csv_pairs(pairs: &[(&'static str, f64)]) -> String {
+    pairs
+        .iter()
+        .map(|x| format!("{}={}", x.0, x.1))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn csv_field(s: &str) -> Cow<'_, str> {
+    if s.contains([',', '"', '\n']) {
+        Cow::Owned(format!("\"{}\"", s.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_pairs<S: Serializer>(
+    pairs: &[(&'static str, f64)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(pairs.len()))?;
+    for (name, value) in pairs {
+        map.serialize_entry(name, value)?;
+    }
+
+    map.end()
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for TestKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+#[serde(untagged)]
+enum OutcomeRecord<'a> {
+    Report {
+        test: TestKind,
+        sub_test: usize,
+        #[serde(flatten)]
+        report: &'a TestReport,
+        passed: bool,
+    },
+    Error {
+        test: TestKind,
+        error: &'a Error,
+        message: String,
+    },
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct BatteryRecord<'a> {
+    alpha: f64,
+    passed: bool,
+    results: Vec<OutcomeRecord<'a>>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> From<&'a BatteryReport> for BatteryRecord<'a> {
+    fn from(report: &'a BatteryReport) -> Self {
+        let mut results = Vec::new();
+        for outcome in report.outcomes.iter() {
+            match &outcome.result {
+                Ok(reports) => {
+                    results.extend(
+                        reports
+                            .iter()
+                            .enumerate()
+                            .map(|(i, x)| OutcomeRecord::Report {
+                                test: outcome.test,
+                                sub_test: i,
+                                report: x,
+                                passed: x.passed_at(report.alpha),
+                            }),
+                    )
+                }
+                Err(e) => results.push(OutcomeRecord::Error {
+                    test: outcome.test,
+                    error: e,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        BatteryRecord {
+            alpha: report.alpha,
+            passed: report.passed(),
+            results,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct SummaryRecord<'a> {
+    test: TestKind,
+    sub_test: usize,
+    histogram: &'a [usize; 10],
+    p_value_t: Option<f64>,
+    uniformity_passed: bool,
+    passed: usize,
+    total: usize,
+    proportion: f64,
+    passed_range: (f64, f64),
+    proportion_passed: bool,
+    p_values: &'a [f64],
+}
+
+#[cfg(feature = "serde")]
+impl<'a> From<&'a SubTestSummary> for SummaryRecord<'a> {
+    fn from(summary: &'a SubTestSummary) -> Self {
+        SummaryRecord {
+            test: summary.test,
+            sub_test: summary.index,
+            histogram: &summary.histogram,
+            p_value_t: summary.uniformity,
+            uniformity_passed: summary.uniformity_passed(),
+            passed: summary.passed,
+            total: summary.total(),
+            proportion: summary.proportion(),
+            passed_range: summary.passed_range,
+            proportion_passed: summary.proportion_passed(),
+            p_values: &summary.p_values,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct AssessmentRecord<'a> {
+    alpha: f64,
+    stream_len: usize,
+    streams: usize,
+    passed: bool,
+    summaries: Vec<SummaryRecord<'a>>,
+    sequences: Vec<BatteryRecord<'a>>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> From<&'a AssessmentReport> for AssessmentRecord<'a> {
+    fn from(report: &'a AssessmentReport) -> Self {
+        AssessmentRecord {
+            alpha: report.alpha,
+            stream_len: report.stream_len,
+            streams: report.sequences.len(),
+            passed: report.passed(),
+            summaries: report.summaries.iter().map(SummaryRecord::from).collect(),
+            sequences: report.sequences.iter().map(BatteryRecord::from).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battery::{BatteryReport, TestKind, TestOutcome};
+    use crate::{Error, TestReport};
+
+    fn battery_report() -> BatteryReport {
+        BatteryReport {
+            alpha: 0.01,
+            outcomes: vec![
+                TestOutcome {
+                    test: TestKind::Frequency,
+                    result: Ok(vec![TestReport::new("Frequency", 0.5)
+                        .with_parameter("n", 100_f64)
+                        .with_statistic("s_n", -4_f64)
+                        .with_statistic("s_obs", 0.4)]),
+                },
+                TestOutcome {
+                    test: TestKind::CumulativeSums,
+                    result: Ok(vec![
+                        TestReport::new("CumulativeSums", 0.005),
+                        TestReport::new("CumulativeSums", 0.25),
+                    ]),
+                },
+                TestOutcome {
+                    test: TestKind::RandomExcursions,
+                    result: Err(Error::NotApplicable {
+                        cycles: 10,
+                        required: 500,
+                    }),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn battery_csv() {
+        let mut out = Vec::new();
+        battery_report().write_csv(&mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "test,sub_test,p_value,passed,parameters,statistics,error\n\
+             Frequency,0,0.5,true,n=100,s_n=-4;s_obs=0.4,\n\
+             CumulativeSums,0,0.005,false,,,\n\
+             CumulativeSums,1,0.25,true,,,\n\
+             RandomExcursions,,,,,,\"test not applicable, there are an insufficient number of \
+             cycles: 10 (required: 500)\"\n"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn battery_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&battery_report().to_json().unwrap()).unwrap();
+
+        assert_eq!(json["alpha"], 0.01);
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["results"].as_array().unwrap().len(), 4);
+
+        let freq = &json["results"][0];
+        assert_eq!(freq["test"], "Frequency");
+        assert_eq!(freq["sub_test"], 0);
+        assert_eq!(freq["parameters"]["n"], 100.0);
+        assert_eq!(freq["statistics"]["s_obs"], 0.4);
+        assert_eq!(freq["p_value"], 0.5);
+        assert_eq!(freq["passed"], true);
+
+        assert_eq!(json["results"][1]["passed"], false);
+
+        let excursions = &json["results"][3];
+        assert_eq!(excursions["test"], "RandomExcursions");
+        assert_eq!(excursions["error"]["kind"], "NotApplicable");
+        assert_eq!(excursions["error"]["cycles"], 10);
+    }
+}
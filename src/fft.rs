@@ -10,7 +10,20 @@ use rustfft::{num_complex::Complex, Fft, FftPlanner, FftPlannerAvx, FftPlannerSs
 /// of this test is to detect periodic features (i.e., repetitive patterns that are near each other) in the tested
 /// sequence that would indicate a deviation from the assumption of randomness. The intention is to detect
 /// whether the number of peaks exceeding the 95 % threshold is significantly different than 5 %.
-pub fn fft_test(data: &BitsData) -> TestResultT {
+/// # Errors
+/// [`Error::TooFewBits`] if the sequence is empty.
+pub fn fft_test<'a>(data: impl Into<BitsView<'a>>) -> Result<TestResultT, Error> {
+    fft_test_report(data).map(|x| x.result())
+}
+
+/// Discrete Fourier Transform (Spectral) Test with detailed result.
+///
+/// Statistics: `n0` - expected and `n1` - observed number of peaks below the threshold `t`, `d`.
+/// See [`fft_test`].
+pub fn fft_test_report<'a>(data: impl Into<BitsView<'a>>) -> Result<TestReport, Error> {
+    let data = data.into();
+    check_len(&data, 1)?;
+
     let n = data.len();
 
     type FftType = f64;
@@ -29,7 +42,7 @@ pub fn fft_test(data: &BitsData) -> TestResultT {
     let mut buf = Vec::<Complex<FftType>>::with_capacity(n);
 
     data.iter().for_each(|x| {
-        if *x {
+        if x {
             buf.push(Complex {
                 re: 1_f64,
                 im: 0_f64,
@@ -56,5 +69,10 @@ pub fn fft_test(data: &BitsData) -> TestResultT {
     let d = (count as f64 - 0.95 * n as f64 / 2_f64) / (n as f64 / 4.0 * 0.95 * 0.05).sqrt();
     let p = erfc(d.abs() / 2_f64.sqrt());
 
-    (p > TEST_THRESHOLD, p)
+    Ok(TestReport::new("FFT", p)
+        .with_parameter("n", n as f64)
+        .with_statistic("t", upper_bound)
+        .with_statistic("n0", 0.95 * n as f64 / 2_f64)
+        .with_statistic("n1", count as f64)
+        .with_statistic("d", d))
 }
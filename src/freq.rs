@@ -9,21 +9,113 @@ use super::*;
 /// be expected for a truly random sequence. The test assesses the closeness of the fraction of ones to 1/2, that
 /// is, the number of ones and zeroes in a sequence should be about the same. All subsequent tests depend on
 /// the passing of this test.
+/// # Errors
+/// [`Error::TooFewBits`] if the sequence is empty.
 /// # Example
 /// ```
 /// use nistrs::freq::frequency_test;
 /// use nistrs::BitsData;
-
+///
 /// let data = BitsData::from_text("11001001000011111101101010100010001000010110100011000010001101001100010011000110011000101000101110001100".to_string());
-/// assert_eq!(frequency_test(&data).1, 0.11666446478102338);
+/// assert_eq!(frequency_test(&data).unwrap().1, 0.11666446478102338);
 /// ```
-pub fn frequency_test(data: &super::BitsData) -> TestResultT {
-    let ones = data.ones();
-    let nbits = data.len();
+pub fn frequency_test<'a>(data: impl Into<BitsView<'a>>) -> Result<TestResultT, Error> {
+    frequency_test_report(data).map(|x| x.result())
+}
+
+/// Frequency (Monobit) Test with detailed result.
+///
+/// Statistics: `s_n` - sum of adjusted (-1, +1) digits, `s_obs`.
+/// See [`frequency_test`].
+pub fn frequency_test_report<'a>(data: impl Into<BitsView<'a>>) -> Result<TestReport, Error> {
+    let data = data.into();
+    check_len(&data, 1)?;
+
+    Ok(frequency_report(data.len(), data.ones()))
+}
+
+/// Frequency (Monobit) Test over a stream of unbounded length.
+///
+/// Chunks of the sequence are passed to [`update`](Self::update) or [`update_bits`](Self::update_bits), only
+/// number of bits and ones are kept. Results are equal to ones of [`frequency_test`] on the whole sequence.
+/// # Example
+/// ```
+/// use nistrs::freq::{frequency_test, FrequencyAccumulator};
+/// use nistrs::BitsData;
+///
+/// let mut acc = FrequencyAccumulator::new();
+/// acc.update(&[0x23, 0x44]);
+/// acc.update(&[0x93]);
+///
+/// let data = BitsData::from_binary(vec![0x23, 0x44, 0x93]);
+/// assert_eq!(acc.finalize(), frequency_test(&data));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrequencyAccumulator {
+    n_bits: usize,
+    ones: usize,
+}
+
+impl FrequencyAccumulator {
+    /// Create accumulator of empty sequence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append bytes to the sequence, the most significant bit of every byte first.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.n_bits += bytes.len() * 8;
+        self.ones += bytes.iter().map(|x| x.count_ones() as usize).sum::<usize>();
+    }
+
+    /// Append bits to the sequence.
+    pub fn update_bits<'a>(&mut self, data: impl Into<BitsView<'a>>) {
+        let data = data.into();
+        self.n_bits += data.len();
+        self.ones += data.ones();
+    }
+
+    /// Return number of bits passed so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.n_bits
+    }
+
+    /// Check that no bits were passed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n_bits == 0
+    }
+
+    /// Return result of the test on bits passed so far, see [`frequency_test`].
+    /// # Errors
+    /// [`Error::TooFewBits`] if the sequence is empty.
+    pub fn finalize(&self) -> Result<TestResultT, Error> {
+        self.finalize_report().map(|x| x.result())
+    }
+
+    /// Return detailed result of the test on bits passed so far, see [`frequency_test_report`].
+    /// # Errors
+    /// [`Error::TooFewBits`] if the sequence is empty.
+    pub fn finalize_report(&self) -> Result<TestReport, Error> {
+        if self.n_bits == 0 {
+            return Err(Error::TooFewBits {
+                required: 1,
+                actual: 0,
+            });
+        }
+
+        Ok(frequency_report(self.n_bits, self.ones))
+    }
+}
 
+fn frequency_report(nbits: usize, ones: usize) -> TestReport {
     let sn = 2 * ones as isize - nbits as isize;
     let sobs = sn.abs() as f64 / (nbits as f64).sqrt();
     let p = erfc(sobs / 2.0_f64.sqrt());
 
-    (p >= TEST_THRESHOLD, p)
+    TestReport::new("Frequency", p)
+        .with_parameter("n", nbits as f64)
+        .with_statistic("s_n", sn as f64)
+        .with_statistic("s_obs", sobs)
 }
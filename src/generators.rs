@@ -0,0 +1,510 @@
+//! Generators after the reference implementation (NIST STS 2.1.2, `generators.c`).
+//!
+//! Every generator follows the algorithm and the seed of the reference generator and keeps its state between
+//! sequences, so consecutive calls of [`Generator::generate`] continue one stream. The generators are sources of
+//! test data: their bit streams are not verified against the output of the reference implementation and do not
+//! replace its sample files. Only the LCG (against its closed form) and G-SHA1 (against the example of FIPS
+//! 186-2) are checked against independent results.
+//! # Example
+//! ```
+//! use nistrs::generators::{Generator, Lcg};
+//! use nistrs::prelude::*;
+//!
+//! let data = Lcg::new().generate(10000);
+//! assert_eq!(data.len(), 10000);
+//!
+//! let result = frequency_test(&data).unwrap();
+//! print!("Test passed: {}; P-value: {}", result.0, result.1);
+//! ```
+
+mod bignum;
+
+use crate::BitsData;
+use bignum::Uint;
+
+/// Source of bit sequences.
+pub trait Generator {
+    /// Generate the next sequence of `n` bits.
+    ///
+    /// Generators which produce bits in blocks discard the rest of the last block, as the reference
+    /// implementation does at the end of every stream.
+    fn generate(&mut self, n: usize) -> BitsData;
+
+    /// Generate `count` consecutive sequences of `n` bits as one sequence, e.g. for
+    /// [`Assessment`](crate::assessment::Assessment).
+    fn generate_streams(&mut self, n: usize, count: usize) -> BitsData {
+        let mut res = BitsData::default();
+        for _ in 0..count {
+            for bit in self.generate(n).iter() {
+                res.push(bit);
+            }
+        }
+
+        res
+    }
+}
+
+/// Append the first `bits` bits of `block` to `data` until it contains `n` bits.
+fn push_block(data: &mut BitsData, block: &[u8], bits: usize, n: usize) {
+    for i in 0..bits {
+        if data.len() == n {
+            return;
+        }
+
+        data.push((block[i / 8] >> (7 - i % 8)) & 1 == 1);
+    }
+}
+
+/// Prime modulus of [`Qcg1`] and [`ModExp`].
+const P_512: &str = "987b6a6bf2c56a97291c445409920032499f9ee7ad128301b5d0254aa1a9633fdbd378d40149f1e23a13849f3d45992f5c4c6b7104099bc301f6005f9d8115e1";
+/// Factors of modulus of [`Bbs`] and [`MicaliSchnorr`].
+const BBS_P: &str = "E65097BAEC92E70478CAF4ED0ED94E1C94B154466BFB9EC9BE37B2B0FF8526C222B76E0E915017535AE8B9207250257D0A0C87C0DACEF78E17D1EF9DC44FD91F";
+const BBS_Q: &str = "E029AEFCF8EA2C29D99CB53DD5FA9BC1D0176F5DF8D9110FD16EE21F32E37BA86FF42F00531AD5B8A43073182CC2E15F5C86E8DA059E346777C9A985F7D8A867";
+/// Seed of [`Qcg2`] and [`Ccg`].
+const G_SEED: &str = "7844506a9456c564b8b8538e0cc15aff46c95e69600f084f0657c2401b3c244734b62ea9bb95be4923b9b7e84eeaf1a224894ef0328d44bc3eb3e983644da3f5";
+/// Base of [`ModExp`] and seed of [`Qcg1`].
+const G_BASE: &str = "3844506a9456c564b8b8538e0cc15aff46c95e69600f084f0657c2401b3c244734b62ea9bb95be4923b9b7e84eeaf1a224894ef0328d44bc3eb3e983644da3f5";
+
+/// Linear Congruential Generator: `z = 950706376 * z mod (2^31 - 1)`, bit is `z / (2^31 - 1) >= 0.5`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lcg {
+    z: u64,
+}
+
+impl Lcg {
+    const A: u64 = 950706376;
+    const M: u64 = 2147483647;
+
+    /// Create generator with the reference seed.
+    pub fn new() -> Self {
+        Lcg { z: 23482349 }
+    }
+}
+
+impl Default for Lcg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for Lcg {
+    fn generate(&mut self, n: usize) -> BitsData {
+        let mut res = BitsData::default();
+        for _ in 0..n {
+            self.z = self.z * Lcg::A % Lcg::M;
+            res.push(2 * self.z >= Lcg::M);
+        }
+
+        res
+    }
+}
+
+/// Quadratic Congruential Generator I: `x = x^2 mod p`, 512 bits per step.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Qcg1 {
+    p: Uint,
+    x: Uint,
+}
+
+impl Qcg1 {
+    /// Create generator with the reference seed.
+    pub fn new() -> Self {
+        Qcg1 {
+            p: Uint::from_hex(P_512, 64),
+            x: Uint::from_hex(G_BASE, 64),
+        }
+    }
+}
+
+impl Default for Qcg1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for Qcg1 {
+    fn generate(&mut self, n: usize) -> BitsData {
+        let mut res = BitsData::default();
+        while res.len() < n {
+            self.x = self.x.mul(&self.x).rem(&self.p);
+            push_block(&mut res, &self.x.to_be_bytes(64), 512, n);
+        }
+
+        res
+    }
+}
+
+/// Quadratic Congruential Generator II: `x = 2x^2 + 3x + 1 mod 2^512`, 512 bits per step.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Qcg2 {
+    x: Uint,
+}
+
+impl Qcg2 {
+    /// Create generator with the reference seed.
+    pub fn new() -> Self {
+        Qcg2 {
+            x: Uint::from_hex(G_SEED, 64),
+        }
+    }
+}
+
+impl Default for Qcg2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for Qcg2 {
+    fn generate(&mut self, n: usize) -> BitsData {
+        let mut res = BitsData::default();
+        while res.len() < n {
+            let t = self.x.add(&self.x).add(&Uint::from_u32(3));
+            self.x = t.mul(&self.x).add(&Uint::from_u32(1)).low(512);
+            push_block(&mut res, &self.x.to_be_bytes(64), 512, n);
+        }
+
+        res
+    }
+}
+
+/// Cubic Congruential Generator: `x = x^3 mod 2^512`, 512 bits per step.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ccg {
+    x: Uint,
+}
+
+impl Ccg {
+    /// Create generator with the reference seed.
+    pub fn new() -> Self {
+        Ccg {
+            x: Uint::from_hex(G_SEED, 64),
+        }
+    }
+}
+
+impl Default for Ccg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for Ccg {
+    fn generate(&mut self, n: usize) -> BitsData {
+        let mut res = BitsData::default();
+        while res.len() < n {
+            self.x = self.x.mul(&self.x).low(512).mul(&self.x).low(512);
+            push_block(&mut res, &self.x.to_be_bytes(64), 512, n);
+        }
+
+        res
+    }
+}
+
+/// Exclusive OR Generator: `x[i] = x[i - 1] xor x[i - 127]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Xor {
+    state: [bool; 127],
+}
+
+impl Xor {
+    const SEED: &'static str = "0001011011011001000101111001001010011011101101000100000010101111111010100100001010110110000000000100110000101110011111111100111";
+
+    /// Create generator with the reference seed.
+    pub fn new() -> Self {
+        let mut state = [false; 127];
+        for (x, c) in state.iter_mut().zip(Xor::SEED.bytes()) {
+            *x = c == b'1';
+        }
+
+        Xor { state }
+    }
+}
+
+impl Default for Xor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for Xor {
+    /// Every sequence restarts indexing of the circular state from zero, as the reference implementation does.
+    fn generate(&mut self, n: usize) -> BitsData {
+        let mut res = BitsData::default();
+        for i in 127..n + 127 {
+            let bit = self.state[(i - 1) % 127] ^ self.state[i % 127];
+            self.state[i % 127] = bit;
+            res.push(bit);
+        }
+
+        res
+    }
+}
+
+/// Modular Exponentiation Generator: `x = g^y mod p`, 512 bits per step, the next `y` is 160 least
+/// significant bits of `x`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModExp {
+    p: Uint,
+    g: Uint,
+    y: Uint,
+}
+
+impl ModExp {
+    /// Create generator with the reference seed.
+    pub fn new() -> Self {
+        ModExp {
+            p: Uint::from_hex(P_512, 64),
+            g: Uint::from_hex(G_BASE, 64),
+            y: Uint::from_hex("7AB36982CE1ADF832019CDFEB2393CABDF0214EC", 20),
+        }
+    }
+}
+
+impl Default for ModExp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for ModExp {
+    fn generate(&mut self, n: usize) -> BitsData {
+        let mut res = BitsData::default();
+        while res.len() < n {
+            let x = self.g.mod_pow(&self.y, &self.p);
+            push_block(&mut res, &x.to_be_bytes(64), 512, n);
+            self.y = x.low(160);
+        }
+
+        res
+    }
+}
+
+/// Blum-Blum-Shub Generator: `x = x^2 mod pq`, bit is the least significant bit of `x`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bbs {
+    n: Uint,
+    x: Uint,
+}
+
+impl Bbs {
+    /// Create generator with the reference seed.
+    pub fn new() -> Self {
+        let n = Uint::from_hex(BBS_P, 64).mul(&Uint::from_hex(BBS_Q, 64));
+        let s = Uint::from_hex("10d6333cfac8e30e808d2192f7c0439480da79db9bbca1667d73be9a677ed31311f3b830937763837cb7b1b1dc75f14eea417f84d9625628750de99e7ef1e976", 64);
+        let x = s.mul(&s).rem(&n);
+
+        Bbs { n, x }
+    }
+}
+
+impl Default for Bbs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for Bbs {
+    fn generate(&mut self, n: usize) -> BitsData {
+        let mut res = BitsData::default();
+        for _ in 0..n {
+            self.x = self.x.mul(&self.x).rem(&self.n);
+            res.push(self.x.is_odd());
+        }
+
+        res
+    }
+}
+
+/// Micali-Schnorr Generator: `y = x^11 mod pq`, 837 least significant bits of `y` per step, the next `x` is
+/// 187 most significant bits of `y`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MicaliSchnorr {
+    n: Uint,
+    x: Uint,
+}
+
+impl MicaliSchnorr {
+    const E: u32 = 11;
+    const K: usize = 837;
+
+    /// Create generator with the reference seed.
+    pub fn new() -> Self {
+        MicaliSchnorr {
+            n: Uint::from_hex(BBS_P, 64).mul(&Uint::from_hex(BBS_Q, 64)),
+            x: Uint::from_hex("237c5f791c2cfe47bfb16d2d54a0d60665b20904ec822a6", 24),
+        }
+    }
+}
+
+impl Default for MicaliSchnorr {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for MicaliSchnorr {
+    fn generate(&mut self, n: usize) -> BitsData {
+        let mut res = BitsData::default();
+        while res.len() < n {
+            let y = self.x.mod_pow(&Uint::from_u32(MicaliSchnorr::E), &self.n);
+            let bytes = y.to_be_bytes(128);
+            let tail = &bytes[128 - MicaliSchnorr::K.div_ceil(8)..];
+            let skip = tail.len() * 8 - MicaliSchnorr::K;
+            for i in skip..tail.len() * 8 {
+                if res.len() == n {
+                    break;
+                }
+                res.push((tail[i / 8] >> (7 - i % 8)) & 1 == 1);
+            }
+            self.x = y.shr(MicaliSchnorr::K);
+        }
+
+        res
+    }
+}
+
+/// G Using SHA-1: `G = SHA1(xkey)`, 160 bits per step, `xkey = xkey + G + 1 mod 2^160`.
+///
+/// `G` is the SHA-1 compression function applied to `xkey` padded with zeros to 512 bits, as in FIPS 186-2
+/// (checked against the example of its Appendix 3.5).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GSha1 {
+    xkey: Uint,
+}
+
+impl GSha1 {
+    /// Create generator with the reference seed.
+    pub fn new() -> Self {
+        GSha1 {
+            xkey: Uint::from_hex("ec822a619d6ed5d9492218a7a4c5b15d57c61601", 20),
+        }
+    }
+}
+
+impl Default for GSha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for GSha1 {
+    fn generate(&mut self, n: usize) -> BitsData {
+        let mut res = BitsData::default();
+        while res.len() < n {
+            let mut block = [0_u8; 64];
+            block[..20].copy_from_slice(&self.xkey.to_be_bytes(20));
+            let g = sha1_compress(&block);
+
+            push_block(&mut res, &g, 160, n);
+            self.xkey = self
+                .xkey
+                .add(&Uint::from_be_bytes(&g))
+                .add(&Uint::from_u32(1))
+                .low(160);
+        }
+
+        res
+    }
+}
+
+/// SHA-1 compression of one block with the initial hash value.
+fn sha1_compress(block: &[u8; 64]) -> [u8; 20] {
+    let h = [
+        0x67452301_u32,
+        0xEFCDAB89,
+        0x98BADCFE,
+        0x10325476,
+        0xC3D2E1F0,
+    ];
+
+    let mut w = [0_u32; 80];
+    for (i, x) in block.chunks(4).enumerate() {
+        w[i] = u32::from_be_bytes([x[0], x[1], x[2], x[3]]);
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = h;
+    for (i, x) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5A827999),
+            20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+            _ => (b ^ c ^ d, 0xCA62C1D6),
+        };
+
+        let t = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(*x);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = t;
+    }
+
+    let mut res = [0_u8; 20];
+    for (i, x) in [a, b, c, d, e].iter().enumerate() {
+        res[4 * i..4 * i + 4].copy_from_slice(&x.wrapping_add(h[i]).to_be_bytes());
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha1() {
+        let mut block = [0_u8; 64];
+        block[..4].copy_from_slice(b"abc\x80");
+        block[63] = 24;
+
+        assert_eq!(
+            sha1_compress(&block),
+            [
+                0xa9, 0x99, 0x3e, 0x36, 0x47, 0x06, 0x81, 0x6a, 0xba, 0x3e, 0x25, 0x71, 0x78, 0x50,
+                0xc2, 0x6c, 0x9c, 0xd0, 0xd8, 0x9d
+            ]
+        );
+    }
+
+    #[test]
+    fn g_sha1_fips_186_2() {
+        // Example of FIPS 186-2, Appendix 3.5: XKEY, x_0, XKEY = 1 + XKEY + x_0 mod 2^160 and x_1.
+        let mut generator = GSha1 {
+            xkey: Uint::from_hex("bd029bbe7f51960bcf9edb2b61f06f0feb5a38b6", 20),
+        };
+        let layout = crate::BitLayout::default();
+
+        assert_eq!(
+            generator.generate(160),
+            BitsData::from_hex("2070b3223dba372fde1c0ffc7b2e3b498b260614", layout).unwrap()
+        );
+        assert_eq!(
+            generator.xkey,
+            Uint::from_hex("dd734ee0bd0bcd3badbaeb27dd1eaa5976803ecb", 20)
+        );
+        assert_eq!(
+            generator.generate(160),
+            BitsData::from_hex("3c6c18bacb0f6c55babb13788e20d737a3275116", layout).unwrap()
+        );
+    }
+
+    #[test]
+    fn generate_streams() {
+        let mut lcg = Lcg::new();
+        let first = lcg.generate(100);
+        let second = lcg.generate(100);
+
+        let data = Lcg::new().generate_streams(100, 2);
+        assert_eq!(data.len(), 200);
+        assert!(data.iter().eq(first.iter().chain(second.iter())));
+    }
+}
@@ -0,0 +1,277 @@
+use std::cmp::Ordering;
+
+/// Unsigned integer of arbitrary length, little-endian 32-bit limbs without leading zeros.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Uint(Vec<u32>);
+
+impl Uint {
+    pub(crate) fn from_u32(value: u32) -> Self {
+        Uint(vec![value]).normalized()
+    }
+
+    pub(crate) fn from_be_bytes(bytes: &[u8]) -> Self {
+        let limbs = bytes
+            .rchunks(4)
+            .map(|x| x.iter().fold(0_u32, |acc, b| (acc << 8) | *b as u32))
+            .collect();
+
+        Uint(limbs).normalized()
+    }
+
+    /// Parse `len` bytes from hexadecimal string in the same way as `ahtopb` of the reference implementation:
+    /// a missing last digit is read from the string terminator.
+    pub(crate) fn from_hex(hex: &str, len: usize) -> Self {
+        let digit = |i: usize| -> u8 {
+            let mut c = hex.as_bytes().get(i).copied().unwrap_or(0);
+            if c > b'F' {
+                c = c.wrapping_sub(0x20);
+            }
+            if c > b'9' {
+                c = c.wrapping_sub(7);
+            }
+            c.wrapping_sub(b'0')
+        };
+
+        let bytes: Vec<u8> = (0..len)
+            .map(|i| (digit(2 * i) << 4).wrapping_add(digit(2 * i + 1)))
+            .collect();
+
+        Uint::from_be_bytes(&bytes)
+    }
+
+    /// Return `len` least significant bytes, most significant byte first.
+    pub(crate) fn to_be_bytes(&self, len: usize) -> Vec<u8> {
+        (0..len)
+            .rev()
+            .map(|i| {
+                let limb = self.0.get(i / 4).copied().unwrap_or(0);
+                (limb >> (8 * (i % 4))) as u8
+            })
+            .collect()
+    }
+
+    pub(crate) fn is_odd(&self) -> bool {
+        self.0.first().is_some_and(|x| x & 1 == 1)
+    }
+
+    pub(crate) fn bit_len(&self) -> usize {
+        match self.0.last() {
+            Some(x) => self.0.len() * 32 - x.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn bit(&self, i: usize) -> bool {
+        self.0.get(i / 32).is_some_and(|x| (x >> (i % 32)) & 1 == 1)
+    }
+
+    pub(crate) fn add(&self, other: &Uint) -> Uint {
+        let len = self.0.len().max(other.0.len());
+        let mut res = Vec::with_capacity(len + 1);
+        let mut carry = 0_u64;
+        for i in 0..len {
+            let sum = self.0.get(i).copied().unwrap_or(0) as u64
+                + other.0.get(i).copied().unwrap_or(0) as u64
+                + carry;
+            res.push(sum as u32);
+            carry = sum >> 32;
+        }
+        res.push(carry as u32);
+
+        Uint(res).normalized()
+    }
+
+    pub(crate) fn mul(&self, other: &Uint) -> Uint {
+        let mut res = vec![0_u32; self.0.len() + other.0.len()];
+        for (i, a) in self.0.iter().enumerate() {
+            let mut carry = 0_u64;
+            for (j, b) in other.0.iter().enumerate() {
+                let t = *a as u64 * *b as u64 + res[i + j] as u64 + carry;
+                res[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            res[i + other.0.len()] = carry as u32;
+        }
+
+        Uint(res).normalized()
+    }
+
+    /// Keep `bits` least significant bits (`bits` is a multiple of 32).
+    pub(crate) fn low(&self, bits: usize) -> Uint {
+        Uint(self.0.iter().take(bits / 32).copied().collect()).normalized()
+    }
+
+    pub(crate) fn shr(&self, bits: usize) -> Uint {
+        let (limbs, shift) = (bits / 32, bits % 32);
+        let src = self.0.get(limbs..).unwrap_or(&[]);
+        let res = (0..src.len())
+            .map(|i| match shift {
+                0 => src[i],
+                _ => {
+                    let hi = src.get(i + 1).copied().unwrap_or(0);
+                    (src[i] >> shift) | (hi << (32 - shift))
+                }
+            })
+            .collect();
+
+        Uint(res).normalized()
+    }
+
+    /// Remainder of division by `m` (Knuth, algorithm D).
+    pub(crate) fn rem(&self, m: &Uint) -> Uint {
+        assert!(!m.0.is_empty(), "division by zero!");
+
+        if self.cmp(m) == Ordering::Less {
+            return self.clone();
+        }
+
+        let n = m.0.len();
+        if n == 1 {
+            let d = m.0[0] as u64;
+            let r = self
+                .0
+                .iter()
+                .rev()
+                .fold(0_u64, |r, x| ((r << 32) | *x as u64) % d);
+            return Uint::from_u32(r as u32);
+        }
+
+        let s = m.0[n - 1].leading_zeros();
+        let v = shl_limbs(&m.0, s, n);
+        let mut u = shl_limbs(&self.0, s, self.0.len() + 1);
+
+        for j in (0..u.len() - n).rev() {
+            let num = ((u[j + n] as u64) << 32) | u[j + n - 1] as u64;
+            let mut qhat = num / v[n - 1] as u64;
+            let mut rhat = num % v[n - 1] as u64;
+            while qhat >> 32 != 0 || qhat * v[n - 2] as u64 > ((rhat << 32) | u[j + n - 2] as u64) {
+                qhat -= 1;
+                rhat += v[n - 1] as u64;
+                if rhat >> 32 != 0 {
+                    break;
+                }
+            }
+
+            let mut k = 0_i64;
+            for i in 0..n {
+                let p = qhat * v[i] as u64;
+                let t = u[i + j] as i64 - k - (p & 0xFFFF_FFFF) as i64;
+                u[i + j] = t as u32;
+                k = (p >> 32) as i64 - (t >> 32);
+            }
+            let t = u[j + n] as i64 - k;
+            u[j + n] = t as u32;
+
+            if t < 0 {
+                let mut carry = 0_u64;
+                for i in 0..n {
+                    let t = u[i + j] as u64 + v[i] as u64 + carry;
+                    u[i + j] = t as u32;
+                    carry = t >> 32;
+                }
+                u[j + n] = u[j + n].wrapping_add(carry as u32);
+            }
+        }
+
+        let r = match s {
+            0 => u[..n].to_vec(),
+            _ => (0..n)
+                .map(|i| (u[i] >> s) | (u[i + 1] << (32 - s)))
+                .collect(),
+        };
+
+        Uint(r).normalized()
+    }
+
+    /// `self ^ e mod m`, exponent is processed from the most significant bit.
+    pub(crate) fn mod_pow(&self, e: &Uint, m: &Uint) -> Uint {
+        let base = self.rem(m);
+        let mut res = Uint::from_u32(1).rem(m);
+        for i in (0..e.bit_len()).rev() {
+            res = res.mul(&res).rem(m);
+            if e.bit(i) {
+                res = res.mul(&base).rem(m);
+            }
+        }
+
+        res
+    }
+
+    fn normalized(mut self) -> Self {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+        self
+    }
+}
+
+impl Ord for Uint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .len()
+            .cmp(&other.0.len())
+            .then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+    }
+}
+
+impl PartialOrd for Uint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Shift limbs left by `s < 32` bits into vector of `len` limbs.
+fn shl_limbs(x: &[u32], s: u32, len: usize) -> Vec<u32> {
+    (0..len)
+        .map(|i| {
+            let lo = x.get(i).copied().unwrap_or(0);
+            match (s, i) {
+                (0, _) => lo,
+                (_, 0) => lo << s,
+                _ => (lo << s) | (x.get(i - 1).copied().unwrap_or(0) >> (32 - s)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex() {
+        let x = Uint::from_hex("0102a0FF", 4);
+        assert_eq!(x.to_be_bytes(5), [0, 1, 2, 0xa0, 0xff]);
+        assert_eq!(Uint::from_hex("12a", 2).to_be_bytes(2), [0x12, 0x70]);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Uint::from_hex("fedcba98765432100123456789abcdef0f1e2d3c4b5a6978", 24);
+        let b = Uint::from_hex("0100000000000000000000000000000003", 17);
+        let m = Uint::from_hex("f123456789abcdef", 8);
+        let m2 = Uint::from_hex("fedcba9876543210ffffffff00000001", 16);
+
+        assert_eq!(
+            a.mul(&b),
+            Uint::from_hex(
+                "fedcba98765432100123456789abcdf20bb45d05ae56ffa80369d0369d0369cd2d5a87b4e20f3c68",
+                40
+            )
+        );
+        assert_eq!(a.rem(&m), Uint::from_hex("5fc3af0995509323", 8));
+        assert_eq!(a.shr(100), Uint::from_hex("0fedcba98765432100123456", 12));
+        assert_eq!(
+            Uint::from_u32(3).mod_pow(&Uint::from_u32(1000), &m),
+            Uint::from_hex("ba273fa60b84d77c", 8)
+        );
+        assert_eq!(
+            a.mul(&a).rem(&m2),
+            Uint::from_hex("ec314c380cf59b94c0cebc5ff9e06e0b", 16)
+        );
+        assert_eq!(
+            a.mod_pow(&Uint::from_u32(65537), &m2),
+            Uint::from_hex("d1ca9fe0bb5bca1b3d28b164bf1a5a44", 16)
+        );
+    }
+}
@@ -6,17 +6,21 @@
 //! use nistrs::prelude::*;
 //!
 //! let data = BitsData::from_binary(vec!(0x23, 0x44));
-//! let result = frequency_test(&data);
+//! let result = frequency_test(&data).unwrap();
 //! print!("Test passed: {}; P-value: {}", result.0, result.1);
 //! ```
-use core::slice::Iter;
-use std::{ops::Index, slice::SliceIndex};
-
 pub mod approximate;
+pub mod assessment;
+pub mod battery;
+mod bits;
 pub mod block_freq;
 pub mod cusum;
+mod encoding;
+mod error;
+mod export;
 pub mod fft;
 pub mod freq;
+pub mod generators;
 pub mod linear;
 pub mod longest_run_of_ones;
 pub mod non_overlapping_template;
@@ -24,163 +28,99 @@ pub mod overlapping_template;
 pub mod random_excursions;
 pub mod random_excursions_variant;
 pub mod rank;
+mod report;
+#[cfg(feature = "rand_core")]
+mod rng;
 pub mod runs;
 pub mod serial;
+mod template;
 pub mod universal;
+mod view;
 
+/// Default significance level (`α`) of tests.
 pub const TEST_THRESHOLD: f64 = 0.01;
 
-/// Return type for most tests.
-/// 0 - test passed, 1 - P-value.
-pub type TestResultT = (bool, f64);
-
-type BitsT = Vec<bool>;
-
-/// Structure contained sequence of bit.
-pub struct BitsData {
-    ones: usize,
-    data: BitsT,
+/// Check that a test with `p_value` is passed at significance level `alpha`.
+///
+/// All tests use the same rule: a sequence is considered random if `P-value >= α`.
+/// # Example
+/// ```
+/// use nistrs::is_passed;
+///
+/// assert!(is_passed(0.01, 0.01));
+/// assert!(!is_passed(0.0099, 0.01));
+/// assert!(!is_passed(0.03, 0.05));
+/// ```
+#[inline]
+pub fn is_passed(p_value: f64, alpha: f64) -> bool {
+    p_value >= alpha
 }
 
-impl BitsData {
-    /// Transform byte-vector into a `BitsData`.
-    /// # Example
-    ///
-    /// ```
-    /// use nistrs::BitsData;
-    ///
-    /// let result = BitsData::from_binary(vec![0x12, 0x21]);
-    /// ```
-    pub fn from_binary(data: Vec<u8>) -> Self {
-        let mut res = BitsData {
-            ones: 0,
-            data: BitsT::with_capacity(data.len() * (u8::BITS as usize)),
-        };
-
-        for i in data {
-            res.ones += i.count_ones() as usize;
-            for n in (0..(u8::BITS as isize)).rev() {
-                res.data.push((i >> n & 1) == 1);
-            }
-        }
-
-        res
-    }
-
-    /// Transform a string consisting of `0` and `1` into a `BitsData`.
-    /// # Panic
-    /// Symbol not `0` or `1`.
-    /// # Example
-    ///
-    /// ```
-    /// use nistrs::BitsData;
-    ///
-    /// let result = BitsData::from_text("0010010001010001".to_string());
-    /// ```
-    pub fn from_text(data: String) -> Self {
-        let mut res = BitsData {
-            ones: 0,
-            data: BitsT::with_capacity(data.len()),
-        };
-
-        for i in data.lines() {
-            i.trim().chars().for_each(|x| match x.to_digit(2) {
-                Some(v) => {
-                    if v == 1 {
-                        res.ones += 1;
-                    }
-                    res.data.push(v == 1);
-                }
-                None => {
-                    panic!("incorrect text data!")
-                }
-            })
-        }
-
-        res
-    }
-
-    /// Return number of bits in storage.
-    #[inline]
-    pub fn len(&self) -> usize {
-        self.data.len()
-    }
+/// Return type for most tests.
+/// 0 - test passed at [`TEST_THRESHOLD`], 1 - P-value.
+pub type TestResultT = (bool, f64);
 
-    /// Check that storage of bits is empty.
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+pub use bits::{BitsData, BitsIter};
+pub use encoding::{BitLayout, BitOrder, WordOrder};
+pub use error::Error;
+pub use report::TestReport;
+pub use template::Template;
+pub use view::{BitsView, BitsWindows};
+
+/// Complemented incomplete gamma function, as `igamc` of the reference implementation.
+/// Unlike [`statrs::function::gamma::gamma_ur`], it returns `1` for `x = 0` and `0` for `x = ∞`.
+#[inline]
+pub(crate) fn igamc(a: f64, x: f64) -> f64 {
+    if x <= 0_f64 {
+        return 1_f64;
     }
 
-    /// Return the iterator for bit sequence.
-    #[inline]
-    pub fn iter(&self) -> Iter<bool> {
-        self.data.iter()
+    if x == f64::INFINITY {
+        return 0_f64;
     }
 
-    /// Return number of `1` in sequence.
-    #[inline]
-    pub fn ones(&self) -> usize {
-        self.ones
-    }
+    statrs::function::gamma::gamma_ur(a, x)
 }
 
-impl<I> Index<I> for BitsData
-where
-    I: SliceIndex<[bool]>,
-{
-    type Output = I::Output;
-
-    #[inline]
-    fn index(&self, index: I) -> &Self::Output {
-        &self.data[index]
+/// Check that `data` contains at least `required` bits.
+#[inline]
+pub(crate) fn check_len(data: &BitsView, required: usize) -> Result<(), Error> {
+    if data.len() < required {
+        return Err(Error::TooFewBits {
+            required,
+            actual: data.len(),
+        });
     }
+
+    Ok(())
 }
 
 /// The module imports all NIST tests in library.
 pub mod prelude {
     pub use crate::{
-        approximate::approximate_entropy_test, block_freq::block_frequency_test,
-        cusum::cumulative_sums_test, fft::fft_test, freq::frequency_test,
-        linear::linear_complexity_test, longest_run_of_ones::longest_run_of_ones_test,
-        non_overlapping_template::non_overlapping_template_test,
-        overlapping_template::overlapping_template_test, random_excursions::random_excursions_test,
-        random_excursions_variant::random_excursions_variant_test, rank::rank_test,
-        runs::runs_test, serial::serial_test, universal::universal_test, *,
+        approximate::{approximate_entropy_test, approximate_entropy_test_report},
+        block_freq::{block_frequency_test, block_frequency_test_report},
+        cusum::{cumulative_sums_test, cumulative_sums_test_report},
+        fft::{fft_test, fft_test_report},
+        freq::{frequency_test, frequency_test_report},
+        linear::{linear_complexity_test, linear_complexity_test_report},
+        longest_run_of_ones::{longest_run_of_ones_test, longest_run_of_ones_test_report},
+        non_overlapping_template::{
+            non_overlapping_template_test, non_overlapping_template_test_report,
+            non_overlapping_template_test_report_with, non_overlapping_template_test_with,
+        },
+        overlapping_template::{
+            overlapping_template_test, overlapping_template_test_report,
+            overlapping_template_test_report_with, overlapping_template_test_with, PiMode,
+        },
+        random_excursions::{random_excursions_test, random_excursions_test_report},
+        random_excursions_variant::{
+            random_excursions_variant_test, random_excursions_variant_test_report,
+        },
+        rank::{rank_test, rank_test_report, rank_test_report_with, rank_test_with},
+        runs::{runs_test, runs_test_report},
+        serial::{serial_test, serial_test_report},
+        universal::{universal_test, universal_test_report},
+        *,
     };
 }
-#[cfg(test)]
-mod tests {
-    use crate::BitsData;
-
-    #[test]
-    #[rustfmt::skip]
-    fn from_binary() {
-        let result = BitsData::from_binary(vec![0x12, 0x21]);
-        assert!(result.data.eq(&[
-            false, false, false, true, 
-            false, false, true,  false, 
-            false, false, true,  false,
-            false, false, false, true
-        ]));
-    }
-
-    #[test]
-    #[rustfmt::skip]
-    fn from_text() {
-        let result = BitsData::from_text("0010010001010001".to_string());
-        assert!(result.data.eq(&[
-            false, false, true,  false, 
-            false, true,  false, false, 
-            false, true,  false, true,
-            false, false, false, true
-        ]))
-    }
-
-    #[test]
-    #[should_panic]
-    #[rustfmt::skip]
-    fn from_error_text() {
-        BitsData::from_text("0010020001010001".to_string());
-    }
-}
@@ -3,7 +3,6 @@ extern crate rayon;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use statrs::function::gamma::gamma_ur;
 
 use super::*;
 
@@ -12,7 +11,32 @@ use super::*;
 /// determine whether or not the sequence is complex enough to be considered random. Random sequences
 /// are characterized by longer LFSRs. An LFSR that is too short implies non-randomness.
 /// `m` The length in bits of a block.
-pub fn linear_complexity_test(data: &BitsData, m: usize) -> TestResultT {
+/// # Errors
+/// [`Error::InvalidParameter`] if `m` is `0`, [`Error::TooFewBits`] if the sequence is shorter than `m` bits.
+pub fn linear_complexity_test<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<TestResultT, Error> {
+    linear_complexity_test_report(data, m).map(|x| x.result())
+}
+
+/// Linear Complexity Test with detailed result.
+///
+/// Statistics: `chi2`. Observed counts are `nu` - numbers of blocks in every class of `T`.
+/// See [`linear_complexity_test`].
+pub fn linear_complexity_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<TestReport, Error> {
+    let data = data.into();
+    if m == 0 {
+        return Err(Error::InvalidParameter {
+            name: "m",
+            allowed: "1..".to_string(),
+        });
+    }
+    check_len(&data, m)?;
+
     const K: usize = 6;
     const PI: [f64; 7] = [
         0.01047, 0.03125, 0.12500, 0.50000, 0.25000, 0.06250, 0.020833,
@@ -74,7 +98,7 @@ pub fn linear_complexity_test(data: &BitsData, m: usize) -> TestResultT {
             n_ += 1;
         }
 
-        let t = (sign as f64) * ((l as f64) - mean) as f64 + 2_f64 / 9_f64;
+        let t = (sign as f64) * ((l as f64) - mean) + 2_f64 / 9_f64;
         if t <= -2.5 {
             nu[0].fetch_add(1, Ordering::SeqCst);
         } else if t <= -1.5 {
@@ -98,7 +122,16 @@ pub fn linear_complexity_test(data: &BitsData, m: usize) -> TestResultT {
             / ((n as f64) * PI[i]);
     }
 
-    let p = gamma_ur((K as f64) / 2_f64, chi2 / 2_f64);
-
-    (p > TEST_THRESHOLD, p)
+    let p = igamc((K as f64) / 2_f64, chi2 / 2_f64);
+
+    Ok(TestReport::new("LinearComplexity", p)
+        .with_parameter("m", m as f64)
+        .with_parameter("k", K as f64)
+        .with_parameter("n", n as f64)
+        .with_statistic("chi2", chi2)
+        .with_degrees_of_freedom(K as f64)
+        .with_counts(
+            nu.iter().map(|x| x.load(Ordering::SeqCst) as f64).collect(),
+            PI.iter().map(|x| x * (n as f64)).collect(),
+        ))
 }
@@ -1,5 +1,3 @@
-use statrs::function::gamma::gamma_ur;
-
 use super::*;
 
 const MINIMAL_BITS: usize = 128;
@@ -15,7 +13,7 @@ const MINIMAL_BITS: usize = 128;
 /// ```
 /// use nistrs::longest_run_of_ones::longest_run_of_ones_test;
 /// use nistrs::BitsData;
-
+///
 /// let data = BitsData::from_text("110011000001010101101100010011001110000000000010010011010
 ///                                 1010001000100111101011010000000110101111100110011100110110110001
 ///                                 0110010".to_string());
@@ -23,13 +21,38 @@ const MINIMAL_BITS: usize = 128;
 ///     longest_run_of_ones_test(&data).unwrap().1,
 ///     0.18060931823971144
 /// );
-pub fn longest_run_of_ones_test(data: &BitsData) -> Result<TestResultT, String> {
+/// ```
+pub fn longest_run_of_ones_test<'a>(data: impl Into<BitsView<'a>>) -> Result<TestResultT, Error> {
+    longest_run_of_ones_test_report(data).map(|x| x.result())
+}
+
+/// Test for the Longest Run of Ones in a Block with detailed result.
+///
+/// Statistics: `chi2`. Observed counts are `nu` - numbers of blocks in every class of the longest run.
+/// See [`longest_run_of_ones_test`].
+/// # Example
+/// ```
+/// use nistrs::longest_run_of_ones::longest_run_of_ones_test_report;
+/// use nistrs::BitsData;
+///
+/// let data = BitsData::from_text("110011000001010101101100010011001110000000000010010011010
+///                                 1010001000100111101011010000000110101111100110011100110110110001
+///                                 0110010".to_string());
+/// let report = longest_run_of_ones_test_report(&data).unwrap();
+/// assert_eq!(report.observed, [4_f64, 9_f64, 3_f64, 0_f64]);
+/// assert_eq!(report.degrees_of_freedom, Some(3_f64));
+/// assert!((report.statistic("chi2").unwrap() - 4.882457).abs() < 1e-6);
+/// ```
+pub fn longest_run_of_ones_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
+) -> Result<TestReport, Error> {
+    let data = data.into();
     let n_bits = data.len();
     if n_bits < MINIMAL_BITS {
-        return Err(format!(
-            "{} bit required! In storage: {}",
-            MINIMAL_BITS, n_bits
-        ));
+        return Err(Error::TooFewBits {
+            required: MINIMAL_BITS,
+            actual: n_bits,
+        });
     }
 
     let k: usize;
@@ -84,18 +107,8 @@ pub fn longest_run_of_ones_test(data: &BitsData) -> Result<TestResultT, String>
     }
 
     let n_blocks = n_bits / m;
-    for i in 0..n_blocks {
-        let mut max_runs = usize::default();
-        let mut cur_runs = usize::default();
-
-        for j in 0..m {
-            if data[i * m + j] {
-                cur_runs += 1;
-                max_runs = max_runs.max(cur_runs);
-            } else {
-                cur_runs = 0;
-            }
-        }
+    for block in data.chunks_exact(m) {
+        let max_runs = longest_run(block);
 
         if max_runs < v[0] {
             nu[0] += 1;
@@ -117,7 +130,85 @@ pub fn longest_run_of_ones_test(data: &BitsData) -> Result<TestResultT, String>
         chi2 += ((nu[i] as f64) - (n_blocks as f64) * pi[i]).powi(2) / ((n_blocks as f64) * pi[i]);
     }
 
-    let p = gamma_ur((k as f64) / 2_f64, chi2 / 2_f64);
+    let p = igamc((k as f64) / 2_f64, chi2 / 2_f64);
+
+    Ok(TestReport::new("LongestRun", p)
+        .with_parameter("m", m as f64)
+        .with_parameter("k", k as f64)
+        .with_parameter("n_blocks", n_blocks as f64)
+        .with_statistic("chi2", chi2)
+        .with_degrees_of_freedom(k as f64)
+        .with_counts(
+            nu.iter().take(k + 1).map(|x| *x as f64).collect(),
+            pi.iter()
+                .take(k + 1)
+                .map(|x| x * (n_blocks as f64))
+                .collect(),
+        ))
+}
+
+/// Return length of the longest run of ones in `data`.
+fn longest_run(data: BitsView) -> usize {
+    let mut max_run = 0_u32;
+    let mut cur_run = 0_u32;
+    data.for_each_word(0, data.len(), |word, count| {
+        // Align bits to the most significant one, unused bits are zeros.
+        let word = word << (64 - count);
+        let count = count as u32;
+
+        let leading = word.leading_ones();
+        if leading == count {
+            cur_run += count;
+            max_run = max_run.max(cur_run);
+            return;
+        }
+
+        max_run = max_run.max(cur_run + leading);
+
+        // Every step shortens all runs by one bit.
+        let mut x = word;
+        let mut len = 0;
+        while x != 0 {
+            x &= x << 1;
+            len += 1;
+        }
+        max_run = max_run.max(len);
+
+        cur_run = (word >> (64 - count)).trailing_ones();
+    });
 
-    Ok((p >= TEST_THRESHOLD, p))
+    max_run as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_run() {
+        let mut bytes: Vec<u8> = (0..512_u32).map(|x| (x * 97 + x / 5) as u8).collect();
+        bytes[100..120].fill(0xFF);
+        bytes[300..309].fill(0xFF);
+        let data = BitsData::from_binary(bytes);
+
+        for (begin, len) in [
+            (0, 4096),
+            (3, 64),
+            (5, 1000),
+            (790, 300),
+            (2390, 120),
+            (60, 1),
+        ] {
+            let view = data.slice(begin..begin + len);
+            let expected = view
+                .iter()
+                .fold((0, 0), |(max, cur), x| match x {
+                    true => (max.max(cur + 1), cur + 1),
+                    false => (max, 0),
+                })
+                .0;
+
+            assert_eq!(super::longest_run(view), expected);
+        }
+    }
 }
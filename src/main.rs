@@ -0,0 +1,380 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, ValueEnum};
+
+use nistrs::assessment::Assessment;
+use nistrs::battery::{Battery, BatteryConfig, TestKind};
+use nistrs::{BitLayout, BitsData};
+
+/// Format of input file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Raw bytes, the most significant bit of every byte first.
+    Binary,
+    /// ASCII `0` and `1`, whitespace is skipped.
+    Ascii,
+    /// Binary mode of the reference implementation: every stream starts at a 32-bit boundary.
+    Sts,
+    /// Hexadecimal digits, whitespace is skipped.
+    Hex,
+    /// Base64 text, whitespace is skipped.
+    Base64,
+}
+
+/// Order of bits in every byte (or word).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum BitOrder {
+    /// The most significant bit first.
+    Msb,
+    /// The least significant bit first.
+    Lsb,
+}
+
+/// Byte order of words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum WordOrder {
+    /// Every byte is a separate unit.
+    Bytes,
+    /// Little-endian 16-bit words.
+    Le16,
+    /// Little-endian 32-bit words.
+    Le32,
+    /// Little-endian 64-bit words.
+    Le64,
+}
+
+/// Class probabilities of the Overlapping Template test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum PiMode {
+    /// Tabulated by SP 800-22 rev1a for the standard parameters.
+    Exact,
+    /// Compound Poisson approximation, as the reference implementation.
+    Approximation,
+}
+
+/// Assess a bit sequence with the NIST SP 800-22 statistical tests.
+///
+/// Exit status is 0 if all tests passed, 1 if any test failed or could not be run and 2 on error or if no
+/// test produced a P-value.
+#[derive(Debug, Parser)]
+#[command(name = "nistrs", version)]
+struct Args {
+    /// Input file.
+    file: PathBuf,
+
+    /// Format of input file.
+    #[arg(short, long, value_enum, default_value_t = Format::Binary)]
+    format: Format,
+
+    /// Number of bytes to skip at the beginning of file.
+    #[arg(long, default_value_t = 0)]
+    offset: u64,
+
+    /// Order of bits for the `binary`, `hex` and `base64` formats.
+    #[arg(long, value_enum, default_value_t = BitOrder::Msb)]
+    bit_order: BitOrder,
+
+    /// Byte order of words for the `binary`, `hex` and `base64` formats.
+    #[arg(long, value_enum, default_value_t = WordOrder::Bytes)]
+    word_order: WordOrder,
+
+    /// Length of every sequence in bits. By default the whole input is split into `streams` sequences.
+    #[arg(short = 'n', long)]
+    stream_len: Option<usize>,
+
+    /// Number of sequences.
+    #[arg(short, long, default_value_t = 1)]
+    streams: usize,
+
+    /// Comma separated names or numbers (1..=15) of tests to run. By default all tests are run.
+    #[arg(short, long, value_delimiter = ',')]
+    tests: Vec<TestKind>,
+
+    /// Significance level.
+    #[arg(short, long, default_value_t = nistrs::TEST_THRESHOLD)]
+    alpha: f64,
+
+    /// Block length for the Block Frequency test.
+    #[arg(long, default_value_t = 128)]
+    block_frequency_m: usize,
+
+    /// Number of rows of every matrix for the Rank test.
+    #[arg(long, default_value_t = 32)]
+    rank_m: usize,
+
+    /// Number of columns of every matrix for the Rank test.
+    #[arg(long, default_value_t = 32)]
+    rank_q: usize,
+
+    /// Template length for the Non-overlapping Template test.
+    #[arg(long, default_value_t = 9)]
+    non_overlapping_template_m: usize,
+
+    /// Number of blocks for the Non-overlapping Template test.
+    #[arg(long, default_value_t = 8)]
+    non_overlapping_template_n_blocks: usize,
+
+    /// Template length for the Overlapping Template test.
+    #[arg(long, default_value_t = 9)]
+    overlapping_template_m: usize,
+
+    /// Class probabilities for the Overlapping Template test.
+    #[arg(long, value_enum, default_value_t = PiMode::Approximation)]
+    overlapping_template_pi: PiMode,
+
+    /// Block length for the Approximate Entropy test.
+    #[arg(long, default_value_t = 10)]
+    approximate_entropy_m: usize,
+
+    /// Block length for the Serial test.
+    #[arg(long, default_value_t = 16)]
+    serial_m: usize,
+
+    /// Block length for the Linear Complexity test.
+    #[arg(long, default_value_t = 500)]
+    linear_complexity_m: usize,
+
+    /// Write report in the layout of `finalAnalysisReport.txt` to file.
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// Write results in JSON to file.
+    #[arg(long)]
+    json: Option<PathBuf>,
+
+    /// Write results in CSV to file.
+    #[arg(long)]
+    csv: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<bool, Box<dyn std::error::Error>> {
+    if args.streams == 0 {
+        return Err("number of streams must be positive".into());
+    }
+
+    if args.streams == 1 && args.report.is_some() {
+        return Err("`--report` requires more than one stream".into());
+    }
+
+    let limit = match args.stream_len {
+        Some(n) => Some(
+            n.checked_mul(args.streams)
+                .ok_or("total length of sequences is too large")?,
+        ),
+        None => None,
+    };
+    let data = load(args, limit)?;
+
+    let stream_len = args.stream_len.unwrap_or(data.len() / args.streams);
+    let config = BatteryConfig {
+        alpha: args.alpha,
+        tests: match args.tests.is_empty() {
+            true => TestKind::ALL.to_vec(),
+            false => args.tests.clone(),
+        },
+        block_frequency_m: args.block_frequency_m,
+        rank_m: args.rank_m,
+        rank_q: args.rank_q,
+        non_overlapping_template_m: args.non_overlapping_template_m,
+        non_overlapping_template_n_blocks: args.non_overlapping_template_n_blocks,
+        overlapping_template_m: args.overlapping_template_m,
+        overlapping_template_pi: match args.overlapping_template_pi {
+            PiMode::Exact => nistrs::overlapping_template::PiMode::Exact,
+            PiMode::Approximation => nistrs::overlapping_template::PiMode::Approximation,
+        },
+        approximate_entropy_m: args.approximate_entropy_m,
+        serial_m: args.serial_m,
+        linear_complexity_m: args.linear_complexity_m,
+    };
+
+    let generator = args.file.display().to_string();
+    let mut stdout = io::stdout().lock();
+
+    if args.streams == 1 {
+        let data = match stream_len < data.len() {
+            true => {
+                let mut data = data;
+                data.truncate(stream_len);
+                data
+            }
+            false => data,
+        };
+
+        let report = Battery::new(config)?.run(&data);
+
+        writeln!(
+            stdout,
+            "{:<24} {:>8} {:>10}  RESULT",
+            "STATISTICAL TEST", "SUB-TEST", "P-VALUE"
+        )?;
+        for outcome in report.outcomes.iter() {
+            match &outcome.result {
+                Ok(reports) => {
+                    for (i, x) in reports.iter().enumerate() {
+                        writeln!(
+                            stdout,
+                            "{:<24} {:>8} {:>10.6}  {}",
+                            outcome.test.name(),
+                            i,
+                            x.p_value,
+                            if x.passed_at(report.alpha) {
+                                "SUCCESS"
+                            } else {
+                                "FAILURE"
+                            }
+                        )?;
+                    }
+                }
+                Err(e) => writeln!(
+                    stdout,
+                    "{:<24} {:>8} {:>10}  {}",
+                    outcome.test.name(),
+                    "-",
+                    "-",
+                    e
+                )?,
+            }
+        }
+
+        if let Some(path) = &args.json {
+            let json = report.to_json()?;
+            write_file(path, |out| write!(out, "{}", json))?;
+        }
+
+        if let Some(path) = &args.csv {
+            write_file(path, |out| report.write_csv(out))?;
+        }
+
+        if report.reports().next().is_none() {
+            return Err("no test produced a P-value".into());
+        }
+
+        return Ok(report.passed());
+    }
+
+    let report = Assessment::new(config, stream_len, args.streams)?.run(&data)?;
+
+    report.write_final_analysis_report(&mut stdout, &generator)?;
+
+    if let Some(path) = &args.report {
+        write_file(path, |out| {
+            report.write_final_analysis_report(out, &generator)
+        })?;
+    }
+
+    if let Some(path) = &args.json {
+        let json = report.to_json()?;
+        write_file(path, |out| write!(out, "{}", json))?;
+    }
+
+    if let Some(path) = &args.csv {
+        write_file(path, |out| report.write_csv(out))?;
+    }
+
+    if report.summaries.iter().all(|x| x.total() == 0) {
+        return Err("no test produced a P-value".into());
+    }
+
+    Ok(report.passed())
+}
+
+fn load(args: &Args, limit: Option<usize>) -> io::Result<BitsData> {
+    let mut file = BufReader::new(File::open(&args.file)?);
+    let layout = BitLayout {
+        bit_order: match args.bit_order {
+            BitOrder::Msb => nistrs::BitOrder::MsbFirst,
+            BitOrder::Lsb => nistrs::BitOrder::LsbFirst,
+        },
+        word_order: match args.word_order {
+            WordOrder::Bytes => nistrs::WordOrder::Bytes,
+            WordOrder::Le16 => nistrs::WordOrder::Le16,
+            WordOrder::Le32 => nistrs::WordOrder::Le32,
+            WordOrder::Le64 => nistrs::WordOrder::Le64,
+        },
+    };
+
+    let mut data = match (args.format, args.stream_len) {
+        (Format::Binary, _) if layout == BitLayout::default() => {
+            return BitsData::from_reader(file, args.offset, limit)
+        }
+        (Format::Binary, _) => {
+            skip(&mut file, args.offset)?;
+            let mut bytes = Vec::new();
+            match limit {
+                Some(n) => {
+                    let size = layout.word_order.size();
+                    let len = n.div_ceil(8 * size) * size;
+                    file.take(len as u64).read_to_end(&mut bytes)?
+                }
+                None => file.read_to_end(&mut bytes)?,
+            };
+            BitsData::from_binary_with(bytes, layout)
+        }
+        (Format::Hex | Format::Base64, _) => {
+            skip(&mut file, args.offset)?;
+            let mut text = String::new();
+            file.read_to_string(&mut text)?;
+            match args.format {
+                Format::Hex => BitsData::from_hex(&text, layout),
+                _ => BitsData::from_base64(&text, layout),
+            }
+        }
+        (Format::Ascii, _) => return BitsData::from_ascii_reader(file, args.offset, limit),
+        (Format::Sts, Some(n)) => {
+            return BitsData::from_sts_binary(file, args.offset, n, args.streams)
+        }
+        (Format::Sts, None) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "`--stream-len` is required for the `sts` format",
+            ))
+        }
+    }
+    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    if let Some(n) = limit {
+        if n < data.len() {
+            data.truncate(n);
+        }
+    }
+
+    Ok(data)
+}
+
+/// Skip `offset` bytes of input, as [`BitsData::from_reader`] does.
+fn skip<R: Read>(reader: &mut R, offset: u64) -> io::Result<()> {
+    if io::copy(&mut reader.by_ref().take(offset), &mut io::sink())? < offset {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "offset is out of input",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Create file at `path`, write it with `f` and flush, so that errors of the last write are not lost.
+fn write_file<F>(path: &Path, f: F) -> io::Result<()>
+where
+    F: FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+{
+    let mut out = BufWriter::new(File::create(path)?);
+    f(&mut out)?;
+    out.flush()
+}
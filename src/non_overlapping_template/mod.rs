@@ -1,35 +1,52 @@
-extern crate rayon;
-use statrs::function::gamma::gamma_ur;
+//! The templates of the reference implementation (files `templates/template<m>`) are all aperiodic templates of
+//! length `m` in ascending order, they are generated on demand by [`templates`]. The tables generated from these
+//! files are kept behind the `reference-templates` feature to check the order.
 
+use std::sync::OnceLock;
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+#[cfg(all(test, feature = "reference-templates"))]
 mod template10;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template11;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template12;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template13;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template14;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template15;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template16;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template2;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template3;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template4;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template5;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template6;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template7;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template8;
+#[cfg(all(test, feature = "reference-templates"))]
 mod template9;
 
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
-
-use self::{
-    template10::TEMPLATE10, template11::TEMPLATE11, template12::TEMPLATE12, template13::TEMPLATE13,
-    template14::TEMPLATE14, template15::TEMPLATE15, template16::TEMPLATE16, template2::TEMPLATE2,
-    template3::TEMPLATE3, template4::TEMPLATE4, template5::TEMPLATE5, template6::TEMPLATE6,
-    template7::TEMPLATE7, template8::TEMPLATE8, template9::TEMPLATE9,
-};
-
 use super::*;
 
 const MAX_NUM_OF_TEMPLATES: usize = 148;
 
+/// Maximal number of blocks recommended by the standard.
+const MAX_BLOCKS: usize = 100;
+
+/// Number of blocks used by [`non_overlapping_template_test`], as in the reference implementation.
+const N: usize = 8;
+
 /// Non-overlapping Template Matching Test.
 /// The focus of this test is the number of occurrences of pre-specified target strings. The purpose of this
 /// test is to detect generators that produce too many occurrences of a given non-periodic (aperiodic) pattern.
@@ -37,17 +54,173 @@ const MAX_NUM_OF_TEMPLATES: usize = 148;
 /// search for a specific m-bit pattern. If the pattern is not found, the window slides one bit position. If the
 /// pattern is found, the window is reset to the bit after the found pattern, and the search resumes.
 /// `m` The length in bits of each template. (2 <= `m` <= 16).
-pub fn non_overlapping_template_test(
-    data: &BitsData,
+/// The sequence is split into `8` blocks and the test uses the first 148 templates of [`templates`], as the
+/// reference implementation does. Use [`non_overlapping_template_test_with`] for another number of blocks.
+/// # Errors
+/// [`Error::InvalidParameter`] if `m` is not in `2..=16`, [`Error::TooFewBits`] if a block is shorter than a
+/// template.
+/// # Example
+/// ```
+/// use nistrs::generators::{Generator, Lcg};
+/// use nistrs::non_overlapping_template::non_overlapping_template_test;
+///
+/// let data = Lcg::new().generate(100000);
+/// assert_eq!(non_overlapping_template_test(&data, 9).unwrap().len(), 148);
+/// assert!(non_overlapping_template_test(&data, 17).is_err());
+/// ```
+pub fn non_overlapping_template_test<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<Vec<TestResultT>, Error> {
+    non_overlapping_template_test_report(data, m).map(|x| x.iter().map(|x| x.result()).collect())
+}
+
+/// Non-overlapping Template Matching Test with detailed result.
+///
+/// Parameters: `template` - the template as number, the first bit is the most significant. Statistics: `lambda`
+/// and `sigma2` - expected value and variance of number of occurrences in a block, `chi2`. Observed counts
+/// are `W_j` - numbers of occurrences of the template in every block, in the order of blocks. The report contains
+/// a warning if the block size is not greater than `0.01 * n`.
+/// See [`non_overlapping_template_test`].
+pub fn non_overlapping_template_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
     m: usize,
-) -> Result<Vec<TestResultT>, String> {
-    if !(2..=16).contains(&m) {
-        return Err("In this implementation 2 <= m <= 16 !".to_string());
+) -> Result<Vec<TestReport>, Error> {
+    non_overlapping_template_test_report_with(data, standard_templates(m)?, N)
+        .map(|x| x.into_iter().map(|x| x.1).collect())
+}
+
+/// The first 148 templates of length `m`, as used by the reference implementation.
+pub(crate) fn standard_templates(m: usize) -> Result<&'static [Template], Error> {
+    let templates = templates(m)?;
+    Ok(&templates[..templates.len().min(MAX_NUM_OF_TEMPLATES)])
+}
+
+/// Non-overlapping Template Matching Test with caller-supplied templates of any length.
+///
+/// Returns result for every template, in the order of `templates`. The expected number of occurrences is derived
+/// for aperiodic templates, the report of a periodic template contains a warning.
+/// `n_blocks` The number of independent blocks `N` the sequence is split into, [`non_overlapping_template_test`]
+/// uses `8`. The standard recommends `N <= 100` and the block size `M > 0.01 * n`.
+/// # Errors
+/// [`Error::InvalidParameter`] if `n_blocks` is not in `1..=100`, [`Error::TooFewBits`] if a block is shorter
+/// than the longest template.
+/// # Example
+/// ```
+/// use nistrs::generators::{Generator, Lcg};
+/// use nistrs::non_overlapping_template::non_overlapping_template_test_with;
+/// use nistrs::Template;
+///
+/// let data = Lcg::new().generate(100000);
+/// let templates = ["000000001", "0011", "10111000"].map(|x| x.parse::<Template>().unwrap());
+/// let results = non_overlapping_template_test_with(&data, &templates, 8).unwrap();
+/// assert_eq!(results[1].0, templates[1]);
+/// assert!(results.iter().all(|(_, (passed, _))| *passed));
+/// ```
+pub fn non_overlapping_template_test_with<'a>(
+    data: impl Into<BitsView<'a>>,
+    templates: &[Template],
+    n_blocks: usize,
+) -> Result<Vec<(Template, TestResultT)>, Error> {
+    non_overlapping_template_test_report_with(data, templates, n_blocks)
+        .map(|x| x.into_iter().map(|(t, x)| (t, x.result())).collect())
+}
+
+/// Non-overlapping Template Matching Test with caller-supplied templates and detailed result.
+///
+/// See [`non_overlapping_template_test_with`] and [`non_overlapping_template_test_report`].
+/// # Example
+/// ```
+/// use nistrs::generators::{Generator, Lcg};
+/// use nistrs::non_overlapping_template::non_overlapping_template_test_report_with;
+/// use nistrs::Template;
+///
+/// let data = Lcg::new().generate(100000);
+/// let templates = ["0011", "0101"].map(|x| x.parse::<Template>().unwrap());
+/// let reports = non_overlapping_template_test_report_with(&data, &templates, 8).unwrap();
+/// assert!(reports[0].1.warnings.is_empty());
+/// assert_eq!(reports[1].1.warnings.len(), 1);
+/// assert_eq!(reports[0].1.observed.len(), 8);
+/// ```
+pub fn non_overlapping_template_test_report_with<'a>(
+    data: impl Into<BitsView<'a>>,
+    templates: &[Template],
+    n_blocks: usize,
+) -> Result<Vec<(Template, TestReport)>, Error> {
+    let data = data.into();
+    if !(1..=MAX_BLOCKS).contains(&n_blocks) {
+        return Err(Error::InvalidParameter {
+            name: "n_blocks",
+            allowed: format!("1..={}", MAX_BLOCKS),
+        });
+    }
+
+    let max_m = templates.iter().map(|x| x.len()).max().unwrap_or(1);
+    check_len(&data, n_blocks * max_m)?;
+
+    let m_blocks = data.len() / n_blocks;
+    let short_blocks = (m_blocks as f64) <= 0.01 * (data.len() as f64);
+
+    Ok(templates
+        .par_iter()
+        .map(|&template| {
+            let report = template_report(&data, template, n_blocks, m_blocks);
+            let report = if short_blocks {
+                report.with_warning(format!(
+                    "block size {} is not greater than 0.01 * n, as the standard recommends",
+                    m_blocks
+                ))
+            } else {
+                report
+            };
+            (template, report)
+        })
+        .collect())
+}
+
+/// Return all aperiodic templates of length `m`, in the order of the reference implementation. Templates are
+/// generated on the first call for every `m`.
+/// # Errors
+/// [`Error::InvalidParameter`] if `m` is not in `2..=16`.
+/// # Example
+/// ```
+/// use nistrs::non_overlapping_template::templates;
+///
+/// let text: Vec<String> = templates(3).unwrap().iter().map(|x| x.to_string()).collect();
+/// assert_eq!(text, ["001", "011", "100", "110"]);
+/// assert!(templates(16).unwrap().iter().all(|x| x.is_aperiodic()));
+/// assert_eq!(templates(9).unwrap().len(), 148);
+/// ```
+pub fn templates(m: usize) -> Result<&'static [Template], Error> {
+    const MIN_M: usize = 2;
+    const MAX_M: usize = 16;
+    static CACHE: [OnceLock<Vec<Template>>; MAX_M - MIN_M + 1] =
+        [const { OnceLock::new() }; MAX_M - MIN_M + 1];
+
+    if !(MIN_M..=MAX_M).contains(&m) {
+        return Err(Error::InvalidParameter {
+            name: "m",
+            allowed: format!("{}..={}", MIN_M, MAX_M),
+        });
     }
 
-    const N: usize = 8;
-    let n_bits = data.len();
-    let m_blocks = n_bits / N;
+    Ok(CACHE[m - MIN_M].get_or_init(|| {
+        (0..1_u64 << m)
+            .map(|x| Template::new(x, m).unwrap())
+            .filter(|x| x.is_aperiodic())
+            .collect()
+    }))
+}
+
+/// Run the test for one template, `data` is split into `n_blocks` blocks of `m_blocks` bits.
+fn template_report(
+    data: &BitsView,
+    template: Template,
+    n_blocks: usize,
+    m_blocks: usize,
+) -> TestReport {
+    let m = template.len();
+    let seq = template.value();
 
     let lambda = ((m_blocks - m + 1) as f64) / 2_f64.powi(m as i32);
     let sqr_var_wj = ((m_blocks as f64)
@@ -55,84 +228,97 @@ pub fn non_overlapping_template_test(
             - (2_f64 * (m as f64) - 1_f64) / 2_f64.powf(2_f64 * (m as f64))))
     .sqrt();
 
-    let num_of_rows = get_template_size(m).min(MAX_NUM_OF_TEMPLATES);
-
-    let par_iter = (0..num_of_rows).into_par_iter().map(|i| {
-        let mut wj = Vec::<usize>::new();
-        wj.resize(N, usize::default());
-
-        let seq = get_tempalte(i, m);
-
-        for (j, cnt) in wj.iter_mut().enumerate().take(N) {
-            let mut begin = j * m_blocks;
-            let mut end = begin + m;
-
-            let mut w_obs = usize::default();
-            let mut k = 0_usize;
-            while k < (m_blocks - m + 1) {
-                if data[begin..end].eq(seq) {
-                    w_obs += 1;
-                    begin += m - 1;
-                    end += m - 1;
-                    k += m - 1;
-                }
-                begin += 1;
-                end += 1;
-                k += 1;
+    let mut wj = Vec::<usize>::new();
+    wj.resize(n_blocks, usize::default());
+
+    for (j, cnt) in wj.iter_mut().enumerate() {
+        let mut begin = j * m_blocks;
+
+        let mut w_obs = usize::default();
+        let mut k = 0_usize;
+        while k < (m_blocks - m + 1) {
+            if data.bits(begin, m) == seq {
+                w_obs += 1;
+                begin += m - 1;
+                k += m - 1;
             }
-            *cnt = w_obs;
+            begin += 1;
+            k += 1;
         }
+        *cnt = w_obs;
+    }
 
-        let mut chi2 = 0_f64;
-        for &j in wj.iter().take(N) {
-            chi2 += (((j as f64) - lambda) / sqr_var_wj).powi(2);
-        }
+    let mut chi2 = 0_f64;
+    for &j in wj.iter() {
+        chi2 += (((j as f64) - lambda) / sqr_var_wj).powi(2);
+    }
 
-        let p = gamma_ur(N as f64 / 2_f64, chi2 / 2_f64);
-        (p >= TEST_THRESHOLD, p)
-    });
+    let p = igamc(n_blocks as f64 / 2_f64, chi2 / 2_f64);
 
-    Ok(par_iter.collect())
-}
+    let report = TestReport::new("NonOverlappingTemplate", p)
+        .with_parameter("m", m as f64)
+        .with_parameter("n_blocks", n_blocks as f64)
+        .with_parameter("block_size", m_blocks as f64)
+        .with_parameter("template", seq as f64)
+        .with_statistic("lambda", lambda)
+        .with_statistic("sigma2", sqr_var_wj.powi(2))
+        .with_statistic("chi2", chi2)
+        .with_degrees_of_freedom(n_blocks as f64)
+        .with_counts(
+            wj.iter().map(|x| *x as f64).collect(),
+            vec![lambda; n_blocks],
+        );
 
-fn get_tempalte(i: usize, m: usize) -> &'static [bool] {
-    match m {
-        2 => &TEMPLATE2[i],
-        3 => &TEMPLATE3[i],
-        4 => &TEMPLATE4[i],
-        5 => &TEMPLATE5[i],
-        6 => &TEMPLATE6[i],
-        7 => &TEMPLATE7[i],
-        8 => &TEMPLATE8[i],
-        9 => &TEMPLATE9[i],
-        10 => &TEMPLATE10[i],
-        11 => &TEMPLATE11[i],
-        12 => &TEMPLATE12[i],
-        13 => &TEMPLATE13[i],
-        14 => &TEMPLATE14[i],
-        15 => &TEMPLATE15[i],
-        16 => &TEMPLATE16[i],
-        _ => panic!("Unknown size! M = {}", m),
+    if template.is_aperiodic() {
+        report
+    } else {
+        report.with_warning(format!(
+            "template {} is periodic, the expected number of occurrences is derived for aperiodic templates",
+            template
+        ))
     }
 }
 
-fn get_template_size(m: usize) -> usize {
-    match m {
-        2 => TEMPLATE2.len(),
-        3 => TEMPLATE3.len(),
-        4 => TEMPLATE4.len(),
-        5 => TEMPLATE5.len(),
-        6 => TEMPLATE6.len(),
-        7 => TEMPLATE7.len(),
-        8 => TEMPLATE8.len(),
-        9 => TEMPLATE9.len(),
-        10 => TEMPLATE10.len(),
-        11 => TEMPLATE11.len(),
-        12 => TEMPLATE12.len(),
-        13 => TEMPLATE13.len(),
-        14 => TEMPLATE14.len(),
-        15 => TEMPLATE15.len(),
-        16 => TEMPLATE16.len(),
-        _ => panic!("Unknown size! M = {}", m),
+#[cfg(all(test, feature = "reference-templates"))]
+mod tests {
+    use super::{
+        template10::TEMPLATE10, template11::TEMPLATE11, template12::TEMPLATE12,
+        template13::TEMPLATE13, template14::TEMPLATE14, template15::TEMPLATE15,
+        template16::TEMPLATE16, template2::TEMPLATE2, template3::TEMPLATE3, template4::TEMPLATE4,
+        template5::TEMPLATE5, template6::TEMPLATE6, template7::TEMPLATE7, template8::TEMPLATE8,
+        template9::TEMPLATE9,
+    };
+
+    fn check<const M: usize>(table: &[[bool; M]]) {
+        let expected: Vec<u64> = table
+            .iter()
+            .map(|x| x.iter().fold(0_u64, |acc, x| (acc << 1) | (*x as u64)))
+            .collect();
+        let generated: Vec<u64> = super::templates(M)
+            .unwrap()
+            .iter()
+            .map(|x| x.value())
+            .collect();
+
+        assert_eq!(generated, expected, "m = {}", M);
+    }
+
+    #[test]
+    fn reference_templates() {
+        check(&TEMPLATE2);
+        check(&TEMPLATE3);
+        check(&TEMPLATE4);
+        check(&TEMPLATE5);
+        check(&TEMPLATE6);
+        check(&TEMPLATE7);
+        check(&TEMPLATE8);
+        check(&TEMPLATE9);
+        check(&TEMPLATE10);
+        check(&TEMPLATE11);
+        check(&TEMPLATE12);
+        check(&TEMPLATE13);
+        check(&TEMPLATE14);
+        check(&TEMPLATE15);
+        check(&TEMPLATE16);
     }
 }
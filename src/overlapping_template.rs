@@ -1,8 +1,34 @@
 use libm::lgamma;
-use statrs::function::gamma::gamma_ur;
 
 use super::*;
 
+/// Block size of the reference implementation.
+pub(crate) const BLOCK_SIZE: usize = 1032;
+
+/// Number of degrees of freedom of the reference implementation.
+pub(crate) const K: usize = 5;
+
+/// Minimal expected number of blocks in every class, as recommended by the standard.
+const MINIMAL_EXPECTED: f64 = 5_f64;
+
+/// Length of template of the tabulated probabilities.
+const TABULATED_M: usize = 9;
+
+/// Class probabilities for the template of 9 ones, `M = 1032` and `K = 5`, tabulated by SP 800-22 rev1a.
+const TABULATED_PI: [f64; K + 1] = [0.364091, 0.185659, 0.139381, 0.100571, 0.070432, 0.139865];
+
+/// Method of computation of class probabilities `π_i` of the Overlapping Template Matching Test.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PiMode {
+    /// Probabilities tabulated by SP 800-22 rev1a for the standard parameters (the template of 9 ones,
+    /// `M = 1032`, `K = 5`); the approximation for other parameters.
+    Exact,
+    /// The compound Poisson approximation for all parameters. The reference implementation computes the same
+    /// probabilities, so `P-values` match its output.
+    #[default]
+    Approximation,
+}
+
 /// Overlapping Template Matching Test.
 /// The focus of the Overlapping Template Matching test is the number of occurrences of pre-specified target
 /// strings. Both this test and the Non-overlapping Template Matching test use an m-bit
@@ -10,31 +36,161 @@ use super::*;
 /// the window slides one bit position. The difference between this test and the test in Non-overlapping Template Matching test is that
 /// when the pattern is found, the window slides only one bit before resuming the search.
 /// `m` - the length in bits of each template.
-pub fn overlapping_template_test(data: &BitsData, m: usize) -> TestResultT {
-    const M: usize = 1032;
+/// The test searches for the template of `m` ones in blocks of 1032 bits with probabilities of
+/// [`PiMode::Approximation`], as the reference implementation does.
+/// # Errors
+/// [`Error::InvalidParameter`] if `m` is not in `1..=64`, [`Error::TooFewBits`] if the sequence is shorter
+/// than one block of 1032 bits.
+pub fn overlapping_template_test<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<TestResultT, Error> {
+    overlapping_template_test_report(data, m).map(|x| x.result())
+}
+
+/// Overlapping Template Matching Test with detailed result.
+///
+/// Parameters: `template` - the template as number, the first bit is the most significant. Statistics: `chi2`.
+/// Observed counts are `nu` - numbers of blocks with 0, 1, ..., 5 or more occurrences.
+/// See [`overlapping_template_test`] and [`overlapping_template_test_report_with`].
+pub fn overlapping_template_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<TestReport, Error> {
+    overlapping_template_test_report_with(data, ones(m)?, BLOCK_SIZE, K, PiMode::Approximation)
+}
+
+/// Return template of `m` ones.
+pub(crate) fn ones(m: usize) -> Result<Template, Error> {
+    if !(1..=64).contains(&m) {
+        return Err(Error::InvalidParameter {
+            name: "m",
+            allowed: "1..=64".to_string(),
+        });
+    }
+
+    Template::new(u64::MAX >> (u64::BITS as usize - m), m)
+}
+
+/// Overlapping Template Matching Test with caller-supplied template, block size and number of classes.
+///
+/// `template` - the template to search for. `block_size` - the length `M` in bits of every block, the reference
+/// implementation uses `1032`. `k` - the number of degrees of freedom `K`: blocks are classified by `0, 1, ...,
+/// K - 1` and `K` or more occurrences, the reference implementation uses `5`. `mode` - method of computation
+/// of class probabilities.
+///
+/// The tabulated probabilities of [`PiMode::Exact`] are only known for the standard parameters. Otherwise class
+/// probabilities are computed by the compound Poisson approximation of the standard, generalized to the period `d`
+/// of template: a run of overlapping occurrences is extended with
+/// probability `2^-d`, occurrences of an aperiodic template have the Poisson distribution. The standard recommends
+/// `m ≈ log2(M)` (so that `λ = (M - m + 1) / 2^m ≈ 2`), `K ≈ 2λ` and more than 5 expected blocks in every class,
+/// the report contains a warning for every recommendation which is not met.
+/// # Errors
+/// [`Error::InvalidParameter`] if `block_size` is less than the length of template or `k` is not in
+/// `1..=block_size - m + 1`, [`Error::TooFewBits`] if the sequence is shorter than one block,
+/// [`Error::InvalidParameter`] if no block is expected in some class, e.g. a template of ones which is much
+/// shorter than `log2(M)`.
+/// # Example
+/// ```
+/// use nistrs::generators::{Generator, Lcg};
+/// use nistrs::overlapping_template::{overlapping_template_test_report_with, PiMode};
+/// use nistrs::Template;
+///
+/// let data = Lcg::new().generate(1000000);
+/// let template = "101100111".parse::<Template>().unwrap();
+/// let report = overlapping_template_test_report_with(&data, template, 1032, 5, PiMode::Exact).unwrap();
+/// assert!(report.passed());
+/// assert!(report.warnings.is_empty());
+///
+/// let report =
+///     overlapping_template_test_report_with(&data, template, 4096, 5, PiMode::Exact).unwrap();
+/// assert_eq!(report.observed.len(), 6);
+/// assert_eq!(report.warnings.len(), 3);
+/// ```
+pub fn overlapping_template_test_with<'a>(
+    data: impl Into<BitsView<'a>>,
+    template: Template,
+    block_size: usize,
+    k: usize,
+    mode: PiMode,
+) -> Result<TestResultT, Error> {
+    overlapping_template_test_report_with(data, template, block_size, k, mode).map(|x| x.result())
+}
+
+/// Overlapping Template Matching Test with caller-supplied parameters and detailed result.
+///
+/// See [`overlapping_template_test_with`] and [`overlapping_template_test_report`].
+pub fn overlapping_template_test_report_with<'a>(
+    data: impl Into<BitsView<'a>>,
+    template: Template,
+    block_size: usize,
+    k: usize,
+    mode: PiMode,
+) -> Result<TestReport, Error> {
+    let m = template.len();
+    if block_size < m {
+        return Err(Error::InvalidParameter {
+            name: "block_size",
+            allowed: format!("{}..", m),
+        });
+    }
+
+    let positions = block_size - m + 1;
+    if !(1..=positions).contains(&k) {
+        return Err(Error::InvalidParameter {
+            name: "k",
+            allowed: format!("1..={}", positions),
+        });
+    }
 
-    let n = data.len() / M;
+    let data = data.into();
+    check_len(&data, block_size)?;
 
-    let pi = compute_pi(M, m);
+    let n = data.len() / block_size;
 
-    let test_seq = vec![true; m];
-    let mut nu: [f64; 6] = [0_f64; 6];
+    let tabulated = mode == PiMode::Exact
+        && block_size == BLOCK_SIZE
+        && k == K
+        && template == ones(TABULATED_M)?;
+    let pi = if tabulated {
+        TABULATED_PI.to_vec()
+    } else {
+        compute_pi(block_size, template, k)
+    };
+
+    // The chi-squared statistic is undefined for a class with zero probability, which is not distinguished from
+    // a very small one in `f64`.
+    if let Some(i) = pi
+        .iter()
+        .position(|x| x.is_nan() || x * (n as f64) <= 0_f64)
+    {
+        return Err(Error::InvalidParameter {
+            name: "block_size",
+            allowed: format!(
+                "sizes with a positive expected number of blocks in every class, {} x {} blocks give {} in class {}",
+                n,
+                block_size,
+                pi[i] * (n as f64),
+                i
+            ),
+        });
+    }
+
+    let test_seq = template.value();
+    let mut nu = vec![0_f64; k + 1];
     let mut begin = usize::default();
-    let mut end = begin + m;
 
     for _ in 0..n {
         let mut w_obs = usize::default();
-        for _ in 0..(M - m + 1) {
-            if data[begin..end].eq(&test_seq) {
+        for _ in 0..positions {
+            if data.bits(begin, m) == test_seq {
                 w_obs += 1;
             }
             begin += 1;
-            end += 1;
         }
 
         begin += m - 1;
-        end += m - 1;
-        w_obs = w_obs.min(nu.len() - 1);
+        w_obs = w_obs.min(k);
         nu[w_obs] += 1_f64;
     }
     let mut chi2 = 0_f64;
@@ -42,38 +198,94 @@ pub fn overlapping_template_test(data: &BitsData, m: usize) -> TestResultT {
         chi2 += (x.0 - x.1 * (n as f64)).powi(2) / (x.1 * (n as f64));
     });
 
-    let p = gamma_ur(2.5_f64, chi2 / 2_f64);
+    let p = igamc(k as f64 / 2_f64, chi2 / 2_f64);
+
+    let mut report = TestReport::new("OverlappingTemplate", p)
+        .with_parameter("m", m as f64)
+        .with_parameter("block_size", block_size as f64)
+        .with_parameter("n", n as f64)
+        .with_parameter("k", k as f64)
+        .with_parameter("template", test_seq as f64)
+        .with_statistic("chi2", chi2)
+        .with_degrees_of_freedom(k as f64);
+
+    let lambda = positions as f64 / 2_f64.powi(m as i32);
+    let best_m = (block_size as f64).log2().round() as usize;
+    if m.abs_diff(best_m) > 1 {
+        report = report.with_warning(format!(
+            "template length {} is far from log2(M) = {}, λ = {} instead of about 2",
+            m, best_m, lambda
+        ));
+    }
+
+    let best_k = (2_f64 * lambda).round() as usize;
+    if k < best_k {
+        report = report.with_warning(format!(
+            "K = {} is less than 2λ = {}, as the standard recommends",
+            k, best_k
+        ));
+    }
+
+    let min_expected = pi.iter().fold(f64::INFINITY, |acc, x| acc.min(*x)) * (n as f64);
+    if min_expected <= MINIMAL_EXPECTED {
+        report = report.with_warning(format!(
+            "expected number of blocks in a class is {}, the standard recommends more than {}",
+            min_expected, MINIMAL_EXPECTED
+        ));
+    }
 
-    (p >= TEST_THRESHOLD, p)
+    Ok(report.with_counts(nu, pi.iter().map(|x| x * (n as f64)).collect()))
 }
 
-fn compute_pi(c_m: usize, m: usize) -> [f64; 6] {
+/// Return probabilities of `0, 1, ..., k - 1` and `k` or more occurrences of `template` in a block of `c_m` bits.
+///
+/// Occurrences are approximated by a compound Poisson distribution: clumps of overlapping occurrences start with
+/// rate `η`, every clump is extended by the next occurrence, shifted by the period `d` of template, with
+/// probability `q = 2^-d`. For the template of ones (`q = 1/2`, `η = λ/2`) this is the approximation of the
+/// standard, for aperiodic templates occurrences can not overlap and have the Poisson distribution.
+fn compute_pi(c_m: usize, template: Template, k: usize) -> Vec<f64> {
+    let m = template.len();
     let lambda = (c_m - m + 1) as f64 / 2_f64.powi(m as i32);
-    let eta = lambda / 2_f64;
+    let q = if template.is_aperiodic() {
+        0_f64
+    } else {
+        2_f64.powi(-(template.period() as i32))
+    };
+    let eta = lambda * (1_f64 - q);
 
     let mut sum = 0_f64;
-    let mut res: [f64; 6] = [0_f64; 6];
+    let mut res = vec![0_f64; k + 1];
 
-    for (i, it) in res.iter_mut().enumerate().take(5) {
-        let tmp = single_compute_pi(i, eta);
+    for (i, it) in res.iter_mut().enumerate().take(k) {
+        let tmp = single_compute_pi(i, eta, q);
         sum += tmp;
 
         *it = tmp;
     }
 
-    res[5] = 1_f64 - sum;
+    res[k] = 1_f64 - sum;
 
     res
 }
 
-fn single_compute_pi(u: usize, eta: f64) -> f64 {
+/// Probability of `u` occurrences: sum over numbers of clumps `l` of probability of `l` clumps and of `u - l`
+/// extensions of them.
+fn single_compute_pi(u: usize, eta: f64, q: f64) -> f64 {
     if u == 0 {
         return (-eta).exp();
     }
 
     let mut sum = 0_f64;
     for l in 1..=u {
-        sum += (-eta - (u as f64) * 2_f64.ln() + (l as f64) * eta.ln() - lgamma(l as f64 + 1_f64)
+        // `(u - l) * ln(q)` is `0` for `l = u`, even if `q = 0`.
+        let extensions = if l == u {
+            0_f64
+        } else {
+            ((u - l) as f64) * q.ln()
+        };
+
+        sum += (-eta + (l as f64) * (1_f64 - q).ln() + extensions + (l as f64) * eta.ln()
+            - lgamma(l as f64 + 1_f64)
             + lgamma(u as f64)
             - lgamma(l as f64)
             - lgamma((u as f64) - (l as f64) + 1_f64))
@@ -82,3 +294,37 @@ fn single_compute_pi(u: usize, eta: f64) -> f64 {
 
     sum
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_pi() {
+        let ones = Template::new(0x1FF, 9).unwrap();
+        let expected = [0.367879, 0.183940, 0.137955, 0.099634, 0.069935, 0.140657];
+        for (x, y) in super::compute_pi(1032, ones, 5).iter().zip(expected) {
+            assert!((x - y).abs() < 1e-6, "{} != {}", x, y);
+        }
+
+        // Occurrences of aperiodic template have the Poisson distribution.
+        let aperiodic = Template::new(1, 9).unwrap();
+        let pi = super::compute_pi(1032, aperiodic, 3);
+        let poisson = [
+            (-2_f64).exp(),
+            2_f64 * (-2_f64).exp(),
+            2_f64 * (-2_f64).exp(),
+        ];
+        for (x, y) in pi.iter().zip(poisson) {
+            assert!((x - y).abs() < 1e-12, "{} != {}", x, y);
+        }
+        assert!((pi.iter().sum::<f64>() - 1_f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn igamc_limits() {
+        // A class with an observed block and an underflowed probability gives an infinite statistic.
+        assert_eq!(crate::igamc(0.5, f64::INFINITY), 0_f64);
+        assert_eq!(crate::igamc(0.5, 0_f64), 1_f64);
+    }
+}
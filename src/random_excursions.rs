@@ -1,5 +1,3 @@
-use statrs::function::gamma::gamma_ur;
-
 use super::*;
 
 /// Random Excursions Test.
@@ -12,7 +10,20 @@ use super::*;
 /// the states: -4, -3, -2, -1 and +1, +2, +3, +4.
 ///
 /// Return `P-value` for 8 state: [-4, -3, -2, -1, 1, 2, 3, 4].
-pub fn random_excursions_test(data: &BitsData) -> Result<[TestResultT; 8], String> {
+pub fn random_excursions_test<'a>(
+    data: impl Into<BitsView<'a>>,
+) -> Result<[TestResultT; 8], Error> {
+    random_excursions_test_report(data).map(|x| x.map(|x| x.result()))
+}
+
+/// Random Excursions Test with detailed result.
+///
+/// Parameters: `x` - the state. Statistics: `j` - number of cycles, `chi2`. Observed counts are `nu` -
+/// numbers of cycles with 0, 1, ..., 5 or more visits of the state.
+/// See [`random_excursions_test`].
+pub fn random_excursions_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
+) -> Result<[TestReport; 8], Error> {
     const STATE_X: [isize; 8] = [-4, -3, -2, -1, 1, 2, 3, 4];
     const PI: [[f64; 6]; 5] = [
         [
@@ -57,6 +68,9 @@ pub fn random_excursions_test(data: &BitsData) -> Result<[TestResultT; 8], Strin
         ],
     ];
 
+    let data = data.into();
+    check_len(&data, 1)?;
+
     let n = data.len();
     let max_iteration = 1000.max(n);
 
@@ -70,10 +84,7 @@ pub fn random_excursions_test(data: &BitsData) -> Result<[TestResultT; 8], Strin
         if s_k[i] == 0 {
             j += 1;
             if j > max_iteration {
-                return Err(
-                    "In RandomExcursionsTest:  EXCEEDING THE MAX NUMBER OF CYCLES EXPECTED"
-                        .to_string(),
-                );
+                return Err(Error::TooManyCycles { max: max_iteration });
             }
 
             cycle[j] = i;
@@ -86,11 +97,12 @@ pub fn random_excursions_test(data: &BitsData) -> Result<[TestResultT; 8], Strin
 
     cycle[j] = data.len();
 
-    if (j as f64) < (0.005 * (n as f64).sqrt()).max(500_f64) {
-        return Err(
-            "WARNING:  TEST NOT APPLICABLE.  THERE ARE AN INSUFFICIENT NUMBER OF CYCLES."
-                .to_string(),
-        );
+    let required = (0.005 * (n as f64).sqrt()).max(500_f64);
+    if (j as f64) < required {
+        return Err(Error::NotApplicable {
+            cycles: j,
+            required: required.ceil() as usize,
+        });
     }
 
     let mut nu: [[usize; 8]; 6] = Default::default();
@@ -121,9 +133,7 @@ pub fn random_excursions_test(data: &BitsData) -> Result<[TestResultT; 8], Strin
         }
     }
 
-    let mut p = [TestResultT::default(); 8];
-
-    for i in 0..STATE_X.len() {
+    let p = std::array::from_fn(|i| {
         let pi_x = &PI[STATE_X[i].unsigned_abs()];
         let mut sum = f64::default();
 
@@ -132,9 +142,18 @@ pub fn random_excursions_test(data: &BitsData) -> Result<[TestResultT; 8], Strin
             sum += (nu[k][i] as f64 - tmp).powi(2) / tmp;
         }
 
-        let tmp_p = gamma_ur(2.5, sum / 2_f64);
-        p[i] = (tmp_p >= TEST_THRESHOLD, tmp_p);
-    }
+        let tmp_p = igamc(2.5, sum / 2_f64);
+
+        TestReport::new("RandomExcursions", tmp_p)
+            .with_parameter("x", STATE_X[i] as f64)
+            .with_statistic("j", j as f64)
+            .with_statistic("chi2", sum)
+            .with_degrees_of_freedom(5_f64)
+            .with_counts(
+                nu.iter().map(|x| x[i] as f64).collect(),
+                pi_x.iter().map(|x| x * (j as f64)).collect(),
+            )
+    });
 
     Ok(p)
 }
@@ -9,11 +9,26 @@ use super::*;
 /// conclusions), one test and conclusion for each of the states: -9, -8, …, -1 and +1, +2, …, +9.
 ///
 /// Return `P-value` for 18 state: [-9, -8, -7, -6, -5, -4, -3, -2, -1, 1, 2, 3, 4, 5, 6, 7, 8, 9].
-pub fn random_excursions_variant_test(data: &BitsData) -> Result<[TestResultT; 18], String> {
+pub fn random_excursions_variant_test<'a>(
+    data: impl Into<BitsView<'a>>,
+) -> Result<[TestResultT; 18], Error> {
+    random_excursions_variant_test_report(data).map(|x| x.map(|x| x.result()))
+}
+
+/// Random Excursions Variant Test with detailed result.
+///
+/// Parameters: `x` - the state. Statistics: `j` - number of cycles, `xi` - total number of visits of the state.
+/// See [`random_excursions_variant_test`].
+pub fn random_excursions_variant_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
+) -> Result<[TestReport; 18], Error> {
     const STATE_X: [isize; 18] = [
         -9, -8, -7, -6, -5, -4, -3, -2, -1, 1, 2, 3, 4, 5, 6, 7, 8, 9,
     ];
 
+    let data = data.into();
+    check_len(&data, 1)?;
+
     let n = data.len();
 
     let mut s_k = vec![isize::default(); n];
@@ -31,23 +46,26 @@ pub fn random_excursions_variant_test(data: &BitsData) -> Result<[TestResultT; 1
         j += 1;
     }
 
-    if (j as f64) < (0.005 * (n as f64).sqrt()).max(500_f64) {
-        return Err(
-            "WARNING:  TEST NOT APPLICABLE.  THERE ARE AN INSUFFICIENT NUMBER OF CYCLES."
-                .to_string(),
-        );
+    let required = (0.005 * (n as f64).sqrt()).max(500_f64);
+    if (j as f64) < required {
+        return Err(Error::NotApplicable {
+            cycles: j,
+            required: required.ceil() as usize,
+        });
     }
 
-    let mut res: [TestResultT; 18] = Default::default();
-    for i in 0..STATE_X.len() {
-        let x = STATE_X[i];
+    let res = STATE_X.map(|x| {
         let count = s_k.iter().filter(|v| **v == x).count();
 
         let arg = ((count as f64) - (j as f64)).abs()
             / (2_f64 * (j as f64) * (4_f64 * (x.abs() as f64) - 2_f64)).sqrt();
         let p = erfc(arg);
-        res[i] = (p >= TEST_THRESHOLD, p);
-    }
+
+        TestReport::new("RandomExcursionsVariant", p)
+            .with_parameter("x", x as f64)
+            .with_statistic("j", j as f64)
+            .with_statistic("xi", count as f64)
+    });
 
     Ok(res)
 }
@@ -1,212 +1,230 @@
-extern crate rayon;
-
-use rayon::prelude::*;
-
 use super::*;
-use std::ops::IndexMut;
-use std::sync::atomic::{AtomicUsize, Ordering};
-
-const MATRIX_SIZE: usize = 32;
 
-const MINIMAL_BITS: usize = MATRIX_SIZE * MATRIX_SIZE * 38;
+/// Minimal number of matrices, as recommended by the standard.
+const MINIMAL_MATRICES: usize = 38;
 
-type RowsT = [bool; MATRIX_SIZE];
-type MatrixT = [RowsT; MATRIX_SIZE];
+/// Minimal expected number of matrices in every class of rank.
+const MINIMAL_EXPECTED: f64 = 5_f64;
 
-struct BitsMatrix {
-    matrix: MatrixT,
-}
+/// Matrix side used by [`rank_test`], as in the reference implementation.
+const MATRIX_SIZE: usize = 32;
 
 /// Binary Matrix Rank Test.
 /// The focus of the test is the rank of disjoint sub-matrices of the entire sequence. The purpose of this test is
 /// to check for linear dependence among fixed length substrings of the original sequence. Note that this test
 /// also appears in the DIEHARD battery of tests.
-/// # Number of bits must be greater than 38912!
-pub fn rank_test(data: &BitsData) -> Result<TestResultT, String> {
-    static P_32: f64 = p_number(32);
-    static P_31: f64 = p_number(31);
-    static P_30: f64 = 1_f64 - (P_32 + P_31);
-
-    let n_bits = data.len();
-
-    if n_bits < MINIMAL_BITS {
-        return Err(format!(
-            "{} bit required! In storage: {}",
-            MINIMAL_BITS, n_bits
-        ));
-    }
-
-    let n = n_bits / (MATRIX_SIZE * MATRIX_SIZE);
-
-    let f_31 = AtomicUsize::default();
-    let f_32 = AtomicUsize::default();
-
-    (0..n).into_par_iter().for_each(|k| {
-        let mut matrix = BitsMatrix::new(data, k);
-        match comput_rank(&mut matrix) {
-            31 => {
-                f_31.fetch_add(1, Ordering::SeqCst);
-            }
-            32 => {
-                f_32.fetch_add(1, Ordering::SeqCst);
-            }
-            _ => {}
-        }
-    });
-
-    let f_30 = n - (f_31.load(Ordering::SeqCst) + f_32.load(Ordering::SeqCst));
-
-    let chi_squared = (f_32.load(Ordering::SeqCst) as f64 - (n as f64) * P_32).powi(2)
-        / (n as f64 * P_32)
-        + (f_31.load(Ordering::SeqCst) as f64 - (n as f64) * P_31).powi(2) / (n as f64 * P_31)
-        + (f_30 as f64 - (n as f64) * P_30).powi(2) / (n as f64 * P_30);
-
-    let p = (-chi_squared / 2_f64).exp();
-
-    Ok((p >= TEST_THRESHOLD, p))
+/// The test uses `32 x 32` matrices, use [`rank_test_with`] for another shape.
+/// # Errors
+/// [`Error::TooFewBits`] if the sequence contains less than 38 matrices (38912 bits).
+/// # Example
+/// ```
+/// use nistrs::generators::{Generator, Lcg};
+/// use nistrs::rank::rank_test;
+///
+/// let data = Lcg::new().generate(100000);
+/// assert!(rank_test(&data).unwrap().0);
+/// ```
+pub fn rank_test<'a>(data: impl Into<BitsView<'a>>) -> Result<TestResultT, Error> {
+    rank_test_report(data).map(|x| x.result())
 }
 
-#[cfg(feature = "const_fn_floating_point_arithmetic")]
-#[cfg(feature = "const_for")]
-const fn calc_product(r: isize) -> f64 {
-    let mut res = 1_f64;
-
-    for i in 0..r - 1 {
-        res *= (1_f64 - 2_f64.powf(i as f64 - MATRIX_SIZE as f64)).powf(2_f64)
-            / (1_f64 - 2_f64.powf(i as f64 - r as f64));
-    }
+/// Binary Matrix Rank Test with detailed result.
+///
+/// Statistics: `chi2`. Observed counts are numbers of matrices with rank 32, 31 and lower.
+/// See [`rank_test`].
+pub fn rank_test_report<'a>(data: impl Into<BitsView<'a>>) -> Result<TestReport, Error> {
+    rank_test_report_with(data, MATRIX_SIZE, MATRIX_SIZE)
+}
 
-    res
+/// Binary Matrix Rank Test with matrices of any shape.
+///
+/// `m` and `q` are numbers of rows and columns of every matrix, [`rank_test`] uses `32 x 32`, DIEHARD uses
+/// `31 x 31` and `6 x 8`.
+/// # Errors
+/// [`Error::InvalidParameter`] if `m < 2`, `q` is not in `2..=64` or 38 matrices do not fit in `usize` bits,
+/// [`Error::TooFewBits`] if the sequence contains less than 38 matrices, [`Error::InvalidParameter`] if less
+/// than 5 matrices of some class of rank are expected: matrices of very different sides (e.g. `64 x 2`) almost
+/// always have full rank, and `6 x 8` matrices of rank below 5 require about 530 matrices.
+/// # Example
+/// ```
+/// use nistrs::generators::{Generator, Lcg};
+/// use nistrs::rank::rank_test_with;
+///
+/// let data = Lcg::new().generate(100000);
+/// assert!(rank_test_with(&data, 31, 31).unwrap().0);
+/// assert!(rank_test_with(&data, 6, 8).unwrap().0);
+/// ```
+pub fn rank_test_with<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+    q: usize,
+) -> Result<TestResultT, Error> {
+    rank_test_report_with(data, m, q).map(|x| x.result())
 }
 
-cfg_if::cfg_if! {
-    if #[cfg(feature = "const_fn_floating_point_arithmetic")] {
-        const fn p_number(r: isize) -> f64 {
-            2_f64.powf((r as f64)  * ((MATRIX_SIZE as f64) * (MATRIX_SIZE as f64) - (r as f64))) * calc_product(r)
+/// Binary Matrix Rank Test with matrices of any shape and detailed result.
+///
+/// Statistics: `chi2`. Observed counts are numbers of matrices with full rank `min(m, q)`, rank one less and
+/// lower.
+/// See [`rank_test_with`].
+pub fn rank_test_report_with<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+    q: usize,
+) -> Result<TestReport, Error> {
+    if m < 2 {
+        return Err(Error::InvalidParameter {
+            name: "m",
+            allowed: "2..".to_string(),
+        });
+    }
+
+    if !(2..=64).contains(&q) {
+        return Err(Error::InvalidParameter {
+            name: "q",
+            allowed: "2..=64".to_string(),
+        });
+    }
+
+    let data = data.into();
+    let n_bits = data.len();
+    let (matrix_bits, required) = match m
+        .checked_mul(q)
+        .and_then(|x| Some((x, x.checked_mul(MINIMAL_MATRICES)?)))
+    {
+        Some(x) => x,
+        None => {
+            return Err(Error::InvalidParameter {
+                name: "m",
+                allowed: format!("2..={}", usize::MAX / (MINIMAL_MATRICES * q)),
+            })
         }
-    } else {
-        const fn p_number(r: isize) -> f64 {
-            match r {
-                31 => 0.577_576_190_173_204_6,
-                32 => 0.288_788_095_153_841_1,
-                _  => 0_f64
-            }
+    };
+
+    if n_bits < required {
+        return Err(Error::TooFewBits {
+            required,
+            actual: n_bits,
+        });
+    }
+
+    let n = n_bits / matrix_bits;
+    let full_rank = m.min(q);
+
+    let p_full = rank_probability(full_rank, m, q);
+    let p_full_1 = rank_probability(full_rank - 1, m, q);
+    let p_lower = 1_f64 - (p_full + p_full_1);
+
+    // Also rejects a negative `p_lower` caused by cancellation.
+    let min_expected = p_full.min(p_full_1).min(p_lower) * (n as f64);
+    if min_expected < MINIMAL_EXPECTED {
+        return Err(Error::InvalidParameter {
+            name: "m",
+            allowed: format!(
+                "shapes with at least {} expected matrices of every class of rank, {} x {} gives {:.3} for {} matrices",
+                MINIMAL_EXPECTED, m, q, min_expected, n
+            ),
+        });
+    }
+
+    let mut f_full = 0_usize;
+    let mut f_full_1 = 0_usize;
+    let mut rows = vec![0_u64; m];
+    for matrix in data.chunks_exact(matrix_bits) {
+        for (i, row) in rows.iter_mut().enumerate() {
+            *row = matrix.bits(i * q, q);
         }
-    }
-}
 
-impl BitsMatrix {
-    fn new(data: &BitsData, k: usize) -> Self {
-        let mut res = BitsMatrix {
-            matrix: MatrixT::default(),
-        };
-
-        let ind_k = k * MATRIX_SIZE * MATRIX_SIZE;
-        for i in 0..MATRIX_SIZE {
-            let ind_i = i * MATRIX_SIZE;
-            for j in 0..MATRIX_SIZE {
-                res.matrix[i][j] = data[ind_k + j + ind_i];
-            }
+        match gf2_rank(&mut rows) {
+            r if r == full_rank => f_full += 1,
+            r if r + 1 == full_rank => f_full_1 += 1,
+            _ => {}
         }
-
-        res
     }
 
-    fn swap(&mut self, i: usize, j: usize) {
-        self.matrix.swap(i, j)
-    }
-}
-
-impl Index<usize> for BitsMatrix {
-    type Output = RowsT;
-
-    #[inline]
-    fn index(&self, i: usize) -> &Self::Output {
-        self.matrix.index(i)
-    }
-}
+    let f_lower = n - (f_full + f_full_1);
 
-impl IndexMut<usize> for BitsMatrix {
-    #[inline]
-    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
-        self.matrix.index_mut(i)
-    }
-}
+    let chi_squared = (f_full as f64 - (n as f64) * p_full).powi(2) / (n as f64 * p_full)
+        + (f_full_1 as f64 - (n as f64) * p_full_1).powi(2) / (n as f64 * p_full_1)
+        + (f_lower as f64 - (n as f64) * p_lower).powi(2) / (n as f64 * p_lower);
 
-fn perform_elementary_row_operations_forward(i: usize, matrix: &mut BitsMatrix) {
-    for j in (i + 1)..MATRIX_SIZE {
-        if matrix[j][i] {
-            for k in i..MATRIX_SIZE {
-                matrix[j][k] ^= matrix[i][k];
-            }
-        }
-    }
-}
+    let p = (-chi_squared / 2_f64).exp();
 
-fn perform_elementary_row_operations_backward(i: usize, matrix: &mut BitsMatrix) {
-    for j in (0..=((i as isize) - 1)).rev() {
-        if matrix[j as usize][i] {
-            for k in 0..MATRIX_SIZE {
-                matrix[j as usize][k] ^= matrix[i][k];
-            }
-        }
-    }
+    Ok(TestReport::new("Rank", p)
+        .with_parameter("m", m as f64)
+        .with_parameter("q", q as f64)
+        .with_parameter("n", n as f64)
+        .with_statistic("chi2", chi_squared)
+        .with_degrees_of_freedom(2_f64)
+        .with_counts(
+            vec![f_full as f64, f_full_1 as f64, f_lower as f64],
+            vec![
+                (n as f64) * p_full,
+                (n as f64) * p_full_1,
+                (n as f64) * p_lower,
+            ],
+        ))
 }
 
-fn find_unit_element_and_swap_forward(i: usize, matrix: &mut BitsMatrix) -> bool {
-    let mut index = i + 1;
-    while index < MATRIX_SIZE && !matrix[index][i] {
-        index += 1;
+/// Probability that a random binary `m x q` matrix has rank `r`:
+/// `2^(r(q + m - r) - mq) * prod_{i = 0}^{r - 1} (1 - 2^(i - q)) (1 - 2^(i - m)) / (1 - 2^(i - r))`.
+fn rank_probability(r: usize, m: usize, q: usize) -> f64 {
+    let mut res = 2_f64.powi(-(((m - r) * (q - r)) as i32));
+    for i in 0..r as i32 {
+        res *= (1_f64 - 2_f64.powi(i - q as i32)) * (1_f64 - 2_f64.powi(i - m as i32))
+            / (1_f64 - 2_f64.powi(i - r as i32));
     }
 
-    if index < MATRIX_SIZE {
-        matrix.swap(i, index);
-        return true;
-    }
-
-    false
+    res
 }
 
-fn find_unit_element_and_swap_backward(i: usize, matrix: &mut BitsMatrix) -> bool {
-    let mut index = (i as isize) - 1;
-    while index >= 0 && !matrix[index as usize][i] {
-        index -= 1;
-    }
-
-    if index >= 0 {
-        matrix.swap(i, index as usize);
-        return true;
-    }
-
-    false
-}
+/// Return rank over GF(2) of matrix with rows packed into words, `rows` are reduced in place.
+///
+/// Every nonzero row, reduced by the previous ones, is linearly independent of them: its lowest bit becomes
+/// the pivot and is eliminated from the following rows.
+fn gf2_rank(rows: &mut [u64]) -> usize {
+    let mut rank = 0;
+    for i in 0..rows.len() {
+        let row = rows[i];
+        if row == 0 {
+            continue;
+        }
 
-fn determine_rank(matrix: &BitsMatrix) -> usize {
-    let mut rank = MATRIX_SIZE;
-    for el in &matrix.matrix {
-        if el.iter().all(|x| !(*x)) {
-            rank -= 1;
+        rank += 1;
+        let pivot = row & row.wrapping_neg();
+        // The pivot bit is set in about half of random rows, so the row is masked instead of a branch.
+        for x in rows[i + 1..].iter_mut() {
+            *x ^= row & ((*x & pivot != 0) as u64).wrapping_neg();
         }
     }
 
     rank
 }
 
-fn comput_rank(matrix: &mut BitsMatrix) -> usize {
-    let m = MATRIX_SIZE;
-    let tmp = m - 1;
-
-    for i in 0..tmp {
-        if matrix[i][i] || find_unit_element_and_swap_forward(i, matrix) {
-            perform_elementary_row_operations_forward(i, matrix);
-        }
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn rank_probability() {
+        assert_eq!(super::rank_probability(32, 32, 32), 0.288_788_095_153_841_1);
+        assert_eq!(super::rank_probability(31, 32, 32), 0.577_576_190_173_204_6);
+        assert!((super::rank_probability(6, 6, 8) - 0.773118).abs() < 1e-6);
+        assert!((super::rank_probability(5, 6, 8) - 0.217439).abs() < 1e-6);
+        assert!((super::rank_probability(5, 8, 6) - 0.217439).abs() < 1e-6);
     }
 
-    for i in (0..=tmp).rev() {
-        if matrix[i][i] || find_unit_element_and_swap_backward(i, matrix) {
-            perform_elementary_row_operations_backward(i, matrix);
-        }
-    }
+    #[test]
+    fn gf2_rank() {
+        let mut identity: Vec<u64> = (0..32).map(|i| 1 << i).collect();
+        assert_eq!(super::gf2_rank(&mut identity), 32);
+        assert_eq!(super::gf2_rank(&mut [0; 32]), 0);
+
+        // Third row is sum of the first two, fourth row is zero.
+        let mut rows = [0b1011, 0b0110, 0b1101, 0, 0b1000];
+        assert_eq!(super::gf2_rank(&mut rows), 3);
 
-    determine_rank(matrix)
+        // Upper triangular matrix with one dependent row.
+        let mut rows: Vec<u64> = (0..32).map(|i| u32::MAX as u64 >> i).collect();
+        rows[20] = rows[3] ^ rows[7];
+        assert_eq!(super::gf2_rank(&mut rows), 31);
+    }
 }
@@ -0,0 +1,140 @@
+use super::*;
+
+/// Detailed result of a test.
+///
+/// Contains values used to calculate `P-value`: parameters of the test, test statistics and, for chi-square
+/// based tests, observed and expected counts of every class.
+/// # Example
+/// ```
+/// use nistrs::freq::frequency_test_report;
+/// use nistrs::BitsData;
+///
+/// let data = BitsData::from_text("1011010101".to_string());
+/// let report = frequency_test_report(&data).unwrap();
+/// assert_eq!(report.statistic("s_obs"), Some(0.6324555320336759));
+/// assert_eq!(report.result(), (true, 0.5270892568655381));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TestReport {
+    /// Name of the test, as used by the reference implementation.
+    pub name: &'static str,
+    /// Parameters used by the test.
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::export::serialize_pairs")
+    )]
+    pub parameters: Vec<(&'static str, f64)>,
+    /// Test statistics.
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::export::serialize_pairs")
+    )]
+    pub statistics: Vec<(&'static str, f64)>,
+    /// Degrees of freedom of the chi-square distribution, if the test uses it.
+    pub degrees_of_freedom: Option<f64>,
+    /// Observed counts of every class.
+    pub observed: Vec<f64>,
+    /// Expected counts of every class.
+    pub expected: Vec<f64>,
+    /// P-value.
+    pub p_value: f64,
+    /// Warnings about parameters for which the result may be unreliable, e.g. outside the ranges recommended
+    /// by the standard.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub warnings: Vec<String>,
+}
+
+impl TestReport {
+    pub(crate) fn new(name: &'static str, p_value: f64) -> Self {
+        TestReport {
+            name,
+            p_value,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn with_parameter(mut self, name: &'static str, value: f64) -> Self {
+        self.parameters.push((name, value));
+        self
+    }
+
+    pub(crate) fn with_statistic(mut self, name: &'static str, value: f64) -> Self {
+        self.statistics.push((name, value));
+        self
+    }
+
+    pub(crate) fn with_degrees_of_freedom(mut self, dof: f64) -> Self {
+        self.degrees_of_freedom = Some(dof);
+        self
+    }
+
+    pub(crate) fn with_counts(mut self, observed: Vec<f64>, expected: Vec<f64>) -> Self {
+        self.observed = observed;
+        self.expected = expected;
+        self
+    }
+
+    pub(crate) fn with_warning(mut self, warning: String) -> Self {
+        self.warnings.push(warning);
+        self
+    }
+
+    /// Return value of parameter with `name`.
+    pub fn parameter(&self, name: &str) -> Option<f64> {
+        self.parameters.iter().find(|x| x.0 == name).map(|x| x.1)
+    }
+
+    /// Return value of statistic with `name`.
+    pub fn statistic(&self, name: &str) -> Option<f64> {
+        self.statistics.iter().find(|x| x.0 == name).map(|x| x.1)
+    }
+
+    /// Check that test passed at the default significance level [`TEST_THRESHOLD`].
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.passed_at(TEST_THRESHOLD)
+    }
+
+    /// Check that test passed at significance level `alpha`.
+    #[inline]
+    pub fn passed_at(&self, alpha: f64) -> bool {
+        is_passed(self.p_value, alpha)
+    }
+
+    /// Return result in short form at the default significance level [`TEST_THRESHOLD`].
+    #[inline]
+    pub fn result(&self) -> TestResultT {
+        self.result_at(TEST_THRESHOLD)
+    }
+
+    /// Return result in short form at significance level `alpha`.
+    /// # Example
+    /// ```
+    /// use nistrs::freq::frequency_test_report;
+    /// use nistrs::BitsData;
+    ///
+    /// let data = BitsData::from_text("1011010101".to_string());
+    /// let report = frequency_test_report(&data).unwrap();
+    /// assert_eq!(report.result_at(0.001), (true, 0.5270892568655381));
+    /// assert_eq!(report.result_at(0.6), (false, 0.5270892568655381));
+    /// ```
+    #[inline]
+    pub fn result_at(&self, alpha: f64) -> TestResultT {
+        (self.passed_at(alpha), self.p_value)
+    }
+}
+
+impl From<&TestReport> for TestResultT {
+    #[inline]
+    fn from(report: &TestReport) -> Self {
+        report.result()
+    }
+}
+
+impl From<TestReport> for TestResultT {
+    #[inline]
+    fn from(report: TestReport) -> Self {
+        report.result()
+    }
+}
@@ -0,0 +1,130 @@
+use rand_core::RngCore;
+
+use crate::assessment::{Assessment, AssessmentReport};
+use crate::battery::{Battery, BatteryReport};
+use crate::{BitsData, Error};
+
+impl BitsData {
+    /// Generate a sequence of `n` bits with `rng`.
+    ///
+    /// Output of [`RngCore::fill_bytes`] is unpacked as by [`BitsData::from_binary`], the rest of the last byte
+    /// is discarded.
+    /// # Example
+    /// ```
+    /// use nistrs::BitsData;
+    /// # use rand_core::{impls, RngCore};
+    /// # struct Counter(u64);
+    /// # impl RngCore for Counter {
+    /// #     fn next_u32(&mut self) -> u32 { self.next_u64() as u32 }
+    /// #     fn next_u64(&mut self) -> u64 { self.0 += 1; self.0 }
+    /// #     fn fill_bytes(&mut self, dst: &mut [u8]) { impls::fill_bytes_via_next(self, dst) }
+    /// # }
+    ///
+    /// let mut rng = Counter(0); // any `RngCore`, e.g. `rand_chacha::ChaCha20Rng`
+    /// let data = BitsData::from_rng(&mut rng, 1000);
+    /// assert_eq!(data.len(), 1000);
+    /// ```
+    pub fn from_rng<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> Self {
+        BitsData::from_rng_streams(rng, n, 1)
+    }
+
+    /// Generate `count` consecutive sequences of `n` bits with `rng` as one sequence, e.g. for [`Assessment`].
+    ///
+    /// Every sequence starts with a new byte of output, as separate calls of [`BitsData::from_rng`] do.
+    pub fn from_rng_streams<R: RngCore + ?Sized>(rng: &mut R, n: usize, count: usize) -> Self {
+        let mut res = BitsData::default();
+        let mut buf = vec![0_u8; n.div_ceil(8)];
+        for _ in 0..count {
+            rng.fill_bytes(&mut buf);
+            for x in buf[..n / 8].iter() {
+                res.push_bits(*x as u64, 8);
+            }
+
+            let rem = n % 8;
+            if rem != 0 {
+                res.push_bits((buf[n / 8] >> (8 - rem)) as u64, rem);
+            }
+        }
+
+        res
+    }
+}
+
+impl Battery {
+    /// Run all enabled tests on a sequence of `n` bits generated with `rng`.
+    pub fn run_rng<R: RngCore + ?Sized>(&self, rng: &mut R, n: usize) -> BatteryReport {
+        self.run(&BitsData::from_rng(rng, n))
+    }
+}
+
+impl Assessment {
+    /// Run the assessment on sequences generated with `rng`.
+    /// # Errors
+    /// The same as [`Assessment::run`].
+    pub fn run_rng<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<AssessmentReport, Error> {
+        let data = BitsData::from_rng_streams(rng, self.stream_len(), self.streams());
+        self.run(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::{impls, RngCore};
+
+    use crate::battery::{Battery, BatteryConfig, TestKind};
+    use crate::BitsData;
+
+    struct Counter(u8);
+
+    impl RngCore for Counter {
+        fn next_u32(&mut self) -> u32 {
+            impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            for x in dst.iter_mut() {
+                *x = self.0;
+                self.0 = self.0.wrapping_add(1);
+            }
+        }
+    }
+
+    #[test]
+    fn from_rng() {
+        let expected = BitsData::from_binary((0..=255).collect());
+        assert_eq!(BitsData::from_rng(&mut Counter(0), 2048), expected);
+
+        let data = BitsData::from_rng(&mut Counter(0), 2045);
+        assert_eq!(data, expected.slice(..2045).to_bits_data());
+
+        let data = BitsData::from_rng_streams(&mut Counter(0), 12, 3);
+        assert_eq!(
+            data,
+            BitsData::from_text("000000000000000000100000000001000000".to_string())
+        );
+        assert_eq!(data.ones(), 2);
+    }
+
+    #[test]
+    fn run_rng() {
+        let battery = Battery::new(BatteryConfig {
+            tests: vec![TestKind::Frequency, TestKind::Runs],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let report = battery.run_rng(&mut Counter(0), 2048);
+        assert_eq!(
+            report.reports().map(|x| x.1.p_value).collect::<Vec<_>>(),
+            battery
+                .run(&BitsData::from_rng(&mut Counter(0), 2048))
+                .reports()
+                .map(|x| x.1.p_value)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -8,33 +8,151 @@ use super::*;
 /// a bit of the opposite value. The purpose of the runs test is to determine whether the number of runs of
 /// ones and zeros of various lengths is as expected for a random sequence. In particular, this test determines
 /// whether the oscillation between such zeros and ones is too fast or too slow.
+/// # Errors
+/// [`Error::TooFewBits`] if the sequence is empty.
 /// # Example
 /// ```
 /// use nistrs::runs::runs_test;
 /// use nistrs::BitsData;
 ///
 /// let data = BitsData::from_text("11001001000011111101101010100010001000010110100011000010001101001100010011000110011000101000101110000000".to_string());
-/// assert_eq!(runs_test(&data).1, 0.6953317934158357);
+/// assert_eq!(runs_test(&data).unwrap().1, 0.6953317934158357);
 /// ```
-pub fn runs_test(data: &BitsData) -> TestResultT {
-    let n_bits = data.len();
-    let n_ones = data.ones();
+pub fn runs_test<'a>(data: impl Into<BitsView<'a>>) -> Result<TestResultT, Error> {
+    runs_test_report(data).map(|x| runs_result(&x))
+}
 
-    let pi = (n_ones as f64) / (n_bits as f64);
-    if (pi - 0.5).abs() > (2.0 / (n_bits as f64).sqrt()) {
-        return (false, 0.5);
+/// Runs Test with detailed result.
+///
+/// Statistics: `pi` - proportion of ones, `v_obs` - total number of runs. If the frequency prerequisite
+/// fails, `v_obs` is absent and `P-value` is `0`, as in the reference implementation.
+/// See [`runs_test`].
+pub fn runs_test_report<'a>(data: impl Into<BitsView<'a>>) -> Result<TestReport, Error> {
+    let data = data.into();
+    check_len(&data, 1)?;
+
+    let mut acc = RunsAccumulator::new();
+    acc.update_bits(data);
+
+    Ok(runs_report(acc.n_bits, acc.ones, acc.runs))
+}
+
+/// Runs Test over a stream of unbounded length.
+///
+/// Chunks of the sequence are passed to [`update`](Self::update) or [`update_bits`](Self::update_bits), only
+/// number of bits, ones and runs are kept. Results are equal to ones of [`runs_test`] on the whole sequence.
+/// # Example
+/// ```
+/// use nistrs::runs::{runs_test, RunsAccumulator};
+/// use nistrs::BitsData;
+///
+/// let mut acc = RunsAccumulator::new();
+/// acc.update(&[0x23, 0x44]);
+/// acc.update(&[0x93]);
+///
+/// let data = BitsData::from_binary(vec![0x23, 0x44, 0x93]);
+/// assert_eq!(acc.finalize(), runs_test(&data));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunsAccumulator {
+    n_bits: usize,
+    ones: usize,
+    runs: usize,
+    last: bool,
+}
+
+impl RunsAccumulator {
+    /// Create accumulator of empty sequence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append bytes to the sequence, the most significant bit of every byte first.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for x in bytes {
+            self.push_bits(*x as u64, 8);
+        }
+    }
+
+    /// Append bits to the sequence.
+    pub fn update_bits<'a>(&mut self, data: impl Into<BitsView<'a>>) {
+        let data = data.into();
+        data.for_each_word(0, data.len(), |word, count| self.push_bits(word, count));
     }
 
-    let mut v = 1_usize;
-    for i in 1..n_bits {
-        if data[i] != data[i - 1] {
-            v += 1;
+    /// Return number of bits passed so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.n_bits
+    }
+
+    /// Check that no bits were passed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n_bits == 0
+    }
+
+    /// Return result of the test on bits passed so far, see [`runs_test`].
+    /// # Errors
+    /// [`Error::TooFewBits`] if the sequence is empty.
+    pub fn finalize(&self) -> Result<TestResultT, Error> {
+        self.finalize_report().map(|x| runs_result(&x))
+    }
+
+    /// Return detailed result of the test on bits passed so far, see [`runs_test_report`].
+    /// # Errors
+    /// [`Error::TooFewBits`] if the sequence is empty.
+    pub fn finalize_report(&self) -> Result<TestReport, Error> {
+        if self.n_bits == 0 {
+            return Err(Error::TooFewBits {
+                required: 1,
+                actual: 0,
+            });
         }
+
+        Ok(runs_report(self.n_bits, self.ones, self.runs))
+    }
+
+    /// Append `count` least significant bits of `word`, the most significant bit first.
+    fn push_bits(&mut self, word: u64, count: usize) {
+        let first = (word >> (count - 1)) & 1 == 1;
+        if self.n_bits == 0 || first != self.last {
+            self.runs += 1;
+        }
+
+        let mask = (1_u64 << (count - 1)) - 1;
+        self.runs += ((word ^ (word >> 1)) & mask).count_ones() as usize;
+        self.ones += (word & (u64::MAX >> (64 - count))).count_ones() as usize;
+        self.n_bits += count;
+        self.last = word & 1 == 1;
+    }
+}
+
+/// Short form of result of Runs Test, `P-value` is `0.5` if the frequency prerequisite fails.
+fn runs_result(report: &TestReport) -> TestResultT {
+    if report.statistic("v_obs").is_none() {
+        return (false, 0.5);
+    }
+
+    report.result()
+}
+
+fn runs_report(n_bits: usize, n_ones: usize, v: usize) -> TestReport {
+    let pi = (n_ones as f64) / (n_bits as f64);
+    let report = TestReport::new("Runs", 0_f64)
+        .with_parameter("n", n_bits as f64)
+        .with_statistic("pi", pi);
+
+    if (pi - 0.5).abs() > (2.0 / (n_bits as f64).sqrt()) {
+        return report;
     }
 
     let erfc_arg = ((v as f64) - 2_f64 * (n_bits as f64) * pi * (1_f64 - pi)).abs()
         / (2_f64 * pi * (1_f64 - pi) * (2_f64 * (n_bits as f64)).sqrt());
     let p = erfc(erfc_arg);
 
-    (p >= TEST_THRESHOLD, p)
+    TestReport {
+        p_value: p,
+        ..report.with_statistic("v_obs", v as f64)
+    }
 }
@@ -1,7 +1,6 @@
 extern crate rayon;
 
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use statrs::function::gamma::gamma_ur;
 
 use super::*;
 
@@ -12,51 +11,88 @@ use super::*;
 /// sequences have uniformity; that is, every m-bit pattern has the same chance of appearing as every other
 /// m-bit pattern. Note that for m = 1, the Serial test is equivalent to the Frequency test.
 /// `m` the length in bits of a block.
-pub fn serial_test(data: &BitsData, m: usize) -> [TestResultT; 2] {
+/// # Errors
+/// [`Error::TooFewBits`] if the sequence is shorter than 4 bits, [`Error::InvalidParameter`] if `m < 2` or
+/// `2^m` is greater than the length of the sequence.
+/// # Example
+/// ```
+/// use nistrs::serial::serial_test_report;
+/// use nistrs::BitsData;
+///
+/// // The example of the standard: `ψ²_3 = 2.8`, `ψ²_2 = 1.2`, `ψ²_1 = 0.4`.
+/// let data = BitsData::from_text("0011011101".to_string());
+/// let report = serial_test_report(&data, 3).unwrap();
+/// assert!((report[0].p_value - 0.808792).abs() < 1e-6);
+/// assert!((report[1].p_value - 0.670320).abs() < 1e-6);
+///
+/// // `∇ψ² = 1.2 - 0.4`, `∇²ψ² = 1.2 - 2 * 0.4`.
+/// let report = serial_test_report(&data, 2).unwrap();
+/// assert!((report[0].statistic("psi2_m1").unwrap() - 0.4).abs() < 1e-12);
+/// assert!((report[0].p_value - (-0.4_f64).exp()).abs() < 1e-12);
+/// assert!((report[1].p_value - 0.527089).abs() < 1e-6);
+/// ```
+pub fn serial_test<'a>(data: impl Into<BitsView<'a>>, m: usize) -> Result<[TestResultT; 2], Error> {
+    serial_test_report(data, m).map(|x| x.map(|x| x.result()))
+}
+
+/// Serial Test with detailed result.
+///
+/// Statistics: `psi2_m`, `psi2_m1`, `psi2_m2` - values of `ψ²` for `m`, `m - 1` and `m - 2`, `del` - `∇ψ²`
+/// for the first result and `∇²ψ²` for the second one.
+/// See [`serial_test`].
+pub fn serial_test_report<'a>(
+    data: impl Into<BitsView<'a>>,
+    m: usize,
+) -> Result<[TestReport; 2], Error> {
+    let data = data.into();
+    check_len(&data, 4)?;
+
+    let max_m = data.len().ilog2() as usize;
+    if !(2..=max_m).contains(&m) {
+        return Err(Error::InvalidParameter {
+            name: "m",
+            allowed: format!("2..={}", max_m),
+        });
+    }
+
     let psi: Vec<_> = (0..3_usize)
         .into_par_iter()
-        .map(|i| psi2(data, m - i))
+        .map(|i| psi2(&data, m - i))
         .collect();
 
     let del1 = psi[0] - psi[1];
     let del2 = psi[0] - 2_f64 * psi[1] + psi[2];
 
     let p = [
-        gamma_ur(2_f64.powi(m as i32 - 1) / 2_f64, del1 / 2_f64),
-        gamma_ur(2_f64.powi(m as i32 - 2) / 2_f64, del2 / 2_f64),
+        igamc(2_f64.powi(m as i32 - 1) / 2_f64, del1 / 2_f64),
+        igamc(2_f64.powi(m as i32 - 2) / 2_f64, del2 / 2_f64),
     ];
 
-    [
-        (p[0] >= TEST_THRESHOLD, p[0]),
-        (p[1] >= TEST_THRESHOLD, p[1]),
-    ]
+    let report = |p: f64, del: f64, dof: f64| {
+        TestReport::new("Serial", p)
+            .with_parameter("m", m as f64)
+            .with_statistic("psi2_m", psi[0])
+            .with_statistic("psi2_m1", psi[1])
+            .with_statistic("psi2_m2", psi[2])
+            .with_statistic("del", del)
+            .with_degrees_of_freedom(dof)
+    };
+
+    Ok([
+        report(p[0], del1, 2_f64.powi(m as i32 - 1)),
+        report(p[1], del2, 2_f64.powi(m as i32 - 2)),
+    ])
 }
 
 #[inline]
-fn psi2(data: &BitsData, m: usize) -> f64 {
-    if m == 1 {
+fn psi2(data: &BitsView, m: usize) -> f64 {
+    if m == 0 {
         return 0_f64;
     }
 
-    let pow_len = 2_usize.pow(m as u32 + 1) - 1;
-    let mut p = vec![usize::default(); pow_len];
-
     let n = data.len();
-    for i in 0..n {
-        let mut k = 1_usize;
-        for j in 0..m {
-            if data[(i + j) % n] {
-                k = k * 2 + 1;
-            } else {
-                k *= 2;
-            }
-        }
-
-        p[k - 1] += 1;
-    }
-
     let mut sum = f64::default();
-    for i in p.iter().take(pow_len).skip(2_usize.pow(m as u32) - 1) {
+    for i in data.cyclic_pattern_counts(m) {
         sum += i.pow(2) as f64;
     }
 
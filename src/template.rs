@@ -0,0 +1,170 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Template (target pattern) of the template matching tests, from 1 to 64 bits long.
+/// # Example
+/// ```
+/// use nistrs::Template;
+///
+/// let template: Template = "000000001".parse().unwrap();
+/// assert_eq!(template, Template::new(1, 9).unwrap());
+/// assert_eq!(template.to_string(), "000000001");
+/// assert!(template.is_aperiodic());
+/// assert!(!"0101".parse::<Template>().unwrap().is_aperiodic());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Template {
+    value: u64,
+    len: usize,
+}
+
+impl Template {
+    /// Maximal length of template.
+    pub const MAX_LEN: usize = u64::BITS as usize;
+
+    /// Create template of `len` least significant bits of `value`, the first bit is the most significant.
+    /// # Errors
+    /// [`Error::InvalidParameter`] if `len` is not in `1..=64` or `value` does not fit into `len` bits.
+    pub fn new(value: u64, len: usize) -> Result<Self, Error> {
+        if !(1..=Self::MAX_LEN).contains(&len) {
+            return Err(Error::InvalidParameter {
+                name: "len",
+                allowed: format!("1..={}", Self::MAX_LEN),
+            });
+        }
+
+        if len < Self::MAX_LEN && value >> len != 0 {
+            return Err(Error::InvalidParameter {
+                name: "value",
+                allowed: format!("0..{}", 1_u64 << len),
+            });
+        }
+
+        Ok(Template { value, len })
+    }
+
+    /// Return bits of template as number, the first bit is the most significant.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Return length of template in bits.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Always `false`: template contains at least one bit.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check that template is aperiodic: no proper prefix of the template is equal to its suffix, so that two
+    /// occurrences of the template can not overlap. The expected number of occurrences in the Non-overlapping
+    /// Template Matching Test is derived for aperiodic templates.
+    #[inline]
+    pub fn is_aperiodic(&self) -> bool {
+        self.period() == self.len
+    }
+
+    /// Return the smallest shift at which two occurrences of the template can overlap, or the length of template
+    /// if it is aperiodic.
+    /// # Example
+    /// ```
+    /// use nistrs::Template;
+    ///
+    /// assert_eq!("111".parse::<Template>().unwrap().period(), 1);
+    /// assert_eq!("10110".parse::<Template>().unwrap().period(), 3);
+    /// assert_eq!("0011".parse::<Template>().unwrap().period(), 4);
+    /// ```
+    pub fn period(&self) -> usize {
+        (1..self.len)
+            .find(|&shift| {
+                let overlap = self.len - shift;
+                let prefix = self.value >> shift;
+                let suffix = self.value & (u64::MAX >> (u64::BITS as usize - overlap));
+                prefix == suffix
+            })
+            .unwrap_or(self.len)
+    }
+}
+
+impl FromStr for Template {
+    type Err = Error;
+
+    /// Parse template from `0` and `1` characters.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut value = 0_u64;
+        for (i, x) in s.bytes().enumerate() {
+            match x {
+                b'0' | b'1' => value = (value << 1) | (x - b'0') as u64,
+                _ => {
+                    return Err(Error::InvalidCharacter {
+                        offset: i as u64,
+                        byte: x,
+                    })
+                }
+            }
+        }
+
+        // Characters are checked before the length, so a long template does not overflow silently.
+        Template::new(if s.len() > Self::MAX_LEN { 0 } else { value }, s.len())
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:0width$b}", self.value, width = self.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Template};
+
+    #[test]
+    fn parse() {
+        let template: Template = "0010".parse().unwrap();
+        assert_eq!((template.value(), template.len()), (2, 4));
+        assert_eq!(template.to_string(), "0010");
+
+        let long = "1".repeat(64);
+        assert_eq!(long.parse::<Template>().unwrap().value(), u64::MAX);
+        assert!("1".repeat(65).parse::<Template>().is_err());
+        assert!("".parse::<Template>().is_err());
+        assert_eq!(
+            "01x".parse::<Template>(),
+            Err(Error::InvalidCharacter {
+                offset: 2,
+                byte: b'x'
+            })
+        );
+        assert!(Template::new(4, 2).is_err());
+    }
+
+    #[test]
+    fn aperiodic() {
+        for (text, expected) in [
+            ("1", true),
+            ("01", true),
+            ("11", false),
+            ("000000001", true),
+            ("100000001", false),
+            ("0011", true),
+            ("0110", false),
+            ("001001", false),
+            ("0010011", true),
+        ] {
+            assert_eq!(
+                text.parse::<Template>().unwrap().is_aperiodic(),
+                expected,
+                "{}",
+                text
+            );
+        }
+    }
+}
@@ -2,12 +2,25 @@ use libm::erfc;
 
 use super::*;
 
+const MINIMAL_BITS: usize = 387840;
+
 /// Maurer’s “Universal Statistical”.
 /// The focus of this test is the number of bits between matching patterns (a measure that is related to the
 /// length of a compressed sequence). The purpose of the test is to detect whether or not the sequence can be
 /// significantly compressed without loss of information. A significantly compressible sequence is
 /// considered to be non-random.
-pub fn universal_test(data: &BitsData) -> TestResultT {
+/// # Errors
+/// [`Error::TooFewBits`] if the sequence is shorter than 387840 bits, i.e. the block length `L` is less than 6
+/// as in the reference implementation.
+pub fn universal_test<'a>(data: impl Into<BitsView<'a>>) -> Result<TestResultT, Error> {
+    universal_test_report(data).map(|x| x.result())
+}
+
+/// Maurer’s “Universal Statistical” Test with detailed result.
+///
+/// Statistics: `fn` - the observed value, `expected_value` and `sigma` - its expected value and deviation.
+/// See [`universal_test`].
+pub fn universal_test_report<'a>(data: impl Into<BitsView<'a>>) -> Result<TestReport, Error> {
     const EXPECTED_VALUE: [f64; 17] = [
         0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 5.2177052, 6.1962507, 7.1836656, 8.1764248, 9.1723243,
         10.170032, 11.168765, 12.168070, 13.167693, 14.167488, 15.167379,
@@ -18,6 +31,9 @@ pub fn universal_test(data: &BitsData) -> TestResultT {
         3.416, 3.419, 3.421,
     ];
 
+    let data = data.into();
+    check_len(&data, MINIMAL_BITS)?;
+
     let n_bits = data.len();
 
     let l: usize = match n_bits {
@@ -31,8 +47,7 @@ pub fn universal_test(data: &BitsData) -> TestResultT {
         4654080.. => 9,
         2068480.. => 8,
         904960.. => 7,
-        387840.. => 6,
-        _ => 5,
+        _ => 6,
     };
 
     // const double c = 0.7 - 0.8/static_cast<double>(L)
@@ -77,5 +92,11 @@ pub fn universal_test(data: &BitsData) -> TestResultT {
     let arg = (phi - EXPECTED_VALUE[l]).abs() / (2_f64.sqrt() * sigma);
     let p = erfc(arg);
 
-    (p >= TEST_THRESHOLD, p)
+    Ok(TestReport::new("Universal", p)
+        .with_parameter("l", l as f64)
+        .with_parameter("q", q as f64)
+        .with_parameter("k", k as f64)
+        .with_statistic("fn", phi)
+        .with_statistic("expected_value", EXPECTED_VALUE[l])
+        .with_statistic("sigma", sigma))
 }
@@ -0,0 +1,377 @@
+use std::ops::{Bound, Index, RangeBounds};
+
+use crate::{BitsData, BitsIter};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Borrowed view of a range of bits of [`BitsData`].
+///
+/// All tests accept a view (or anything convertible into it, e.g. `&BitsData`), so parts of one capture can be
+/// tested without copying.
+/// # Example
+/// ```
+/// use nistrs::prelude::*;
+///
+/// let data = BitsData::from_text("0110100111".to_string());
+/// let head = data.slice(..5);
+/// assert_eq!(head.ones(), 3);
+/// assert_eq!(frequency_test(head).unwrap(), frequency_test(&head.to_bits_data()).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct BitsView<'a> {
+    data: &'a BitsData,
+    begin: usize,
+    len: usize,
+}
+
+impl<'a> BitsView<'a> {
+    /// Return number of bits in view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check that view is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the iterator for bits of view.
+    #[inline]
+    pub fn iter(&self) -> BitsIter<'a> {
+        BitsIter::new(self.data, self.begin, self.begin + self.len)
+    }
+
+    /// Return number of `1` in view.
+    pub fn ones(&self) -> usize {
+        if self.len == self.data.len() {
+            return self.data.ones();
+        }
+
+        let mut res = 0;
+        self.for_each_word(0, self.len, |word, _| res += word.count_ones() as usize);
+        res
+    }
+
+    /// Return bit with index `i` or `None` if index is out of range.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<bool> {
+        if i < self.len {
+            Some(self.data.bit(self.begin + i))
+        } else {
+            None
+        }
+    }
+
+    /// Return `count` bits started from `begin` as number, the first bit is the most significant.
+    /// # Panic
+    /// `count` is greater than 64 or range is out of view.
+    #[inline]
+    pub fn bits(&self, begin: usize, count: usize) -> u64 {
+        assert!(begin + count <= self.len, "range is out of sequence");
+
+        self.data.bits(self.begin + begin, count)
+    }
+
+    /// Return view of `range` of this view.
+    /// # Panic
+    /// `range` is out of view.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> BitsView<'a> {
+        let begin = match range.start_bound() {
+            Bound::Included(x) => *x,
+            Bound::Excluded(x) => x + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(x) => x + 1,
+            Bound::Excluded(x) => *x,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            begin <= end && end <= self.len,
+            "range {}..{} is out of sequence of length {}",
+            begin,
+            end,
+            self.len
+        );
+
+        BitsView {
+            data: self.data,
+            begin: self.begin + begin,
+            len: end - begin,
+        }
+    }
+
+    /// Return iterator over consecutive sequences of `len` bits, the remaining bits are skipped.
+    /// # Panic
+    /// `len` is `0`.
+    /// # Example
+    /// ```
+    /// use nistrs::BitsData;
+    ///
+    /// let data = BitsData::from_text("0110100111".to_string());
+    /// let ones: Vec<usize> = data.view().chunks_exact(3).map(|x| x.ones()).collect();
+    /// assert_eq!(ones, [2, 1, 2]);
+    /// ```
+    pub fn chunks_exact(&self, len: usize) -> BitsWindows<'a> {
+        self.windows(len, len)
+    }
+
+    /// Return iterator over windows of `len` bits, every window starts `step` bits after the previous one.
+    /// Windows overlap if `step < len`; only windows which fit into the view are returned.
+    /// # Panic
+    /// `len` or `step` is `0`.
+    /// # Example
+    /// ```
+    /// use nistrs::BitsData;
+    ///
+    /// let data = BitsData::from_text("0110100111".to_string());
+    /// let ones: Vec<usize> = data.view().windows(4, 2).map(|x| x.ones()).collect();
+    /// assert_eq!(ones, [2, 2, 2, 3]);
+    /// ```
+    pub fn windows(&self, len: usize, step: usize) -> BitsWindows<'a> {
+        assert!(len != 0, "window length must be positive");
+        assert!(step != 0, "window step must be positive");
+
+        BitsWindows {
+            view: *self,
+            len,
+            step,
+            next: 0,
+        }
+    }
+
+    /// Pass bits of range `begin..end` to `f` by words: `f(word, count)` gets `count` bits as the least
+    /// significant bits of `word`, the first bit is the most significant.
+    #[inline]
+    pub(crate) fn for_each_word<F: FnMut(u64, usize)>(&self, begin: usize, end: usize, mut f: F) {
+        for i in (begin..end).step_by(WORD_BITS) {
+            let count = WORD_BITS.min(end - i);
+            f(self.bits(i, count), count);
+        }
+    }
+
+    /// Count `m`-bit patterns at every position of the view extended by its first `m - 1` bits, as the Serial
+    /// and Approximate Entropy tests do. Counts are indexed by value of pattern, the first bit is the most
+    /// significant.
+    /// # Panic
+    /// `m - 1` is greater than the length of view or `m` is not less than 64.
+    pub(crate) fn cyclic_pattern_counts(&self, m: usize) -> Vec<usize> {
+        let mut counts = vec![0_usize; 1 << m];
+        if m == 0 {
+            counts[0] = self.len;
+            return counts;
+        }
+
+        let mask = (1_usize << m) - 1;
+        let mut k = self.bits(0, m - 1) as usize;
+        let mut push = |word: u64, count: usize| {
+            for i in (0..count).rev() {
+                k = ((k << 1) | ((word >> i) & 1) as usize) & mask;
+                counts[k] += 1;
+            }
+        };
+
+        self.for_each_word(m - 1, self.len, &mut push);
+        self.for_each_word(0, m - 1, &mut push);
+
+        counts
+    }
+
+    /// Copy bits of view into a new `BitsData`.
+    pub fn to_bits_data(&self) -> BitsData {
+        let mut res = BitsData::default();
+        self.for_each_word(0, self.len, |word, count| res.push_bits(word, count));
+        res
+    }
+}
+
+impl BitsData {
+    /// Return view of the whole sequence.
+    #[inline]
+    pub fn view(&self) -> BitsView<'_> {
+        BitsView {
+            data: self,
+            begin: 0,
+            len: self.len(),
+        }
+    }
+
+    /// Return view of `range` of the sequence.
+    /// # Panic
+    /// `range` is out of sequence.
+    #[inline]
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> BitsView<'_> {
+        self.view().slice(range)
+    }
+
+    /// Return iterator over consecutive sequences of `len` bits, see [`BitsView::chunks_exact`].
+    #[inline]
+    pub fn chunks_exact(&self, len: usize) -> BitsWindows<'_> {
+        self.view().chunks_exact(len)
+    }
+
+    /// Return iterator over windows of `len` bits with `step`, see [`BitsView::windows`].
+    #[inline]
+    pub fn windows(&self, len: usize, step: usize) -> BitsWindows<'_> {
+        self.view().windows(len, step)
+    }
+}
+
+impl<'a> From<&'a BitsData> for BitsView<'a> {
+    #[inline]
+    fn from(data: &'a BitsData) -> Self {
+        data.view()
+    }
+}
+
+impl<'a> From<&BitsView<'a>> for BitsView<'a> {
+    #[inline]
+    fn from(view: &BitsView<'a>) -> Self {
+        *view
+    }
+}
+
+impl PartialEq for BitsView<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for BitsView<'_> {}
+
+impl Index<usize> for BitsView<'_> {
+    type Output = bool;
+
+    #[inline]
+    fn index(&self, i: usize) -> &Self::Output {
+        assert!(
+            i < self.len,
+            "index out of bounds: the len is {} but the index is {}",
+            self.len,
+            i
+        );
+
+        if self.data.bit(self.begin + i) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl<'a> IntoIterator for BitsView<'a> {
+    type Item = bool;
+    type IntoIter = BitsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over fixed-length windows of a sequence, see [`BitsView::windows`].
+#[derive(Clone, Debug)]
+pub struct BitsWindows<'a> {
+    view: BitsView<'a>,
+    len: usize,
+    step: usize,
+    next: usize,
+}
+
+impl<'a> Iterator for BitsWindows<'a> {
+    type Item = BitsView<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.view.len < self.len || self.next > self.view.len - self.len {
+            return None;
+        }
+
+        let res = self.view.slice(self.next..self.next + self.len);
+        self.next += self.step;
+        Some(res)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self.view.len.checked_sub(self.len + self.next) {
+            Some(x) => x / self.step + 1,
+            None => 0,
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for BitsWindows<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::BitsData;
+
+    #[test]
+    fn slice() {
+        let data = BitsData::from_binary((0..64_u32).map(|x| (x * 7) as u8).collect());
+
+        for (begin, len) in [(0, 512), (3, 0), (3, 64), (5, 200), (63, 130), (100, 412)] {
+            let view = data.slice(begin..begin + len);
+            assert_eq!(view.len(), len);
+            assert_eq!(view.ones(), view.iter().filter(|x| *x).count());
+            assert!(view.iter().eq(data.iter().skip(begin).take(len)));
+            assert!((0..len).all(|i| view[i] == data[begin + i]));
+
+            let copy = view.to_bits_data();
+            assert_eq!(copy.len(), len);
+            assert_eq!(copy.ones(), view.ones());
+            assert_eq!(copy.view(), view);
+        }
+
+        let view = data.slice(10..=300).slice(20..);
+        assert_eq!(view, data.slice(30..301));
+        assert_eq!(view.bits(0, 64), data.bits(30, 64));
+        assert_eq!(view.get(271), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_out_of_range() {
+        BitsData::from_text("0110".to_string()).slice(2..5);
+    }
+
+    #[test]
+    fn windows() {
+        let data = BitsData::from_binary((0..16).collect());
+
+        let windows = data.windows(32, 24);
+        assert_eq!(windows.len(), 5);
+        for (i, x) in windows.enumerate() {
+            assert_eq!(x, data.slice(24 * i..24 * i + 32));
+        }
+
+        let chunks = data.slice(4..).chunks_exact(40);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.last(), Some(data.slice(84..124)));
+
+        assert_eq!(data.windows(129, 1).len(), 0);
+        assert_eq!(data.windows(129, 1).next(), None);
+        assert_eq!(data.windows(128, 1).count(), 1);
+    }
+
+    #[test]
+    fn cyclic_pattern_counts() {
+        let data = BitsData::from_binary((0..=255).map(|x: u32| (x * 37 + 11) as u8).collect());
+        let view = data.slice(5..2000);
+        let n = view.len();
+
+        for m in [0, 1, 2, 7, 12] {
+            let mut expected = vec![0; 1 << m];
+            for i in 0..n {
+                let k = (0..m).fold(0, |k, j| (k << 1) | view[(i + j) % n] as usize);
+                expected[k] += 1;
+            }
+
+            assert_eq!(view.cyclic_pattern_counts(m), expected);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "cli")]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Write `data` to a temporary file named after the test.
+fn input(name: &str, data: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("nistrs-{}-{}", name, std::process::id()));
+    std::fs::write(&path, data).unwrap();
+    path
+}
+
+fn nistrs(args: &[&str]) -> Option<i32> {
+    Command::new(env!("CARGO_BIN_EXE_nistrs"))
+        .args(args)
+        .output()
+        .unwrap()
+        .status
+        .code()
+}
+
+#[test]
+fn test_empty_input() {
+    let path = input("empty", &[]);
+    let file = path.to_str().unwrap();
+
+    assert_eq!(nistrs(&[file]), Some(2));
+    assert_eq!(nistrs(&[file, "--tests", "frequency"]), Some(2));
+    assert_eq!(nistrs(&[file, "--streams", "10"]), Some(2));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_short_input() {
+    let data: Vec<u8> = (0..2048_u32).map(|x| (x * 37 % 251) as u8).collect();
+    let path = input("short", &data);
+    let file = path.to_str().unwrap();
+
+    assert_eq!(nistrs(&[file, "--tests", "frequency,runs"]), Some(0));
+    // The Universal test requires 387840 bits.
+    assert_eq!(nistrs(&[file, "--tests", "frequency,universal"]), Some(1));
+    assert_eq!(nistrs(&[file, "--tests", "universal"]), Some(2));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_write_error() {
+    let data: Vec<u8> = (0..2048_u32).map(|x| (x * 37 % 251) as u8).collect();
+    let path = input("write", &data);
+    let file = path.to_str().unwrap();
+
+    // Output is buffered, the error of writing to a full device only appears when the file is flushed.
+    assert_eq!(
+        nistrs(&[file, "-t", "frequency", "--json", "/dev/full"]),
+        Some(2)
+    );
+    assert_eq!(
+        nistrs(&[file, "-t", "frequency", "--csv", "/dev/full"]),
+        Some(2)
+    );
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let path = input("arguments", b"0123456789abcdef");
+    let file = path.to_str().unwrap();
+
+    let huge = (usize::MAX / 2).to_string();
+    assert_eq!(nistrs(&[file, "-n", &huge, "-s", "3"]), Some(2));
+
+    for format in [
+        &["--format", "binary"][..],
+        &["--format", "binary", "--bit-order", "lsb"],
+        &["--format", "hex"],
+        &["--format", "base64"],
+        &["--format", "ascii"],
+    ] {
+        let mut args = vec![file, "-t", "frequency", "--offset", "17"];
+        args.extend_from_slice(format);
+        let output = Command::new(env!("CARGO_BIN_EXE_nistrs"))
+            .args(&args)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(2), "{:?}", format);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("offset is out of input"));
+    }
+
+    std::fs::remove_file(path).unwrap();
+}
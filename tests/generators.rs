@@ -0,0 +1,165 @@
+use nistrs::generators::*;
+use nistrs::BitsData;
+
+/// First 128 bits of sequence as two words and number of ones.
+fn check(data: &BitsData, words: [u64; 2], ones: usize) {
+    assert_eq!(data.len(), 1000);
+    assert_eq!([data.bits(0, 64), data.bits(64, 64)], words);
+    assert_eq!(data.ones(), ones);
+}
+
+#[test]
+fn test_lcg() {
+    let mut generator = Lcg::new();
+    check(
+        &generator.generate(1000),
+        [0x88d2bb06a666a439, 0x1bfb0ef600dd02a8],
+        509,
+    );
+    check(
+        &generator.generate(1000),
+        [0xe9a7005e006d0a86, 0xb50783ab42af8129],
+        483,
+    );
+}
+
+#[test]
+fn test_lcg_closed_form() {
+    // `z_k = 950706376^k * z_0 mod (2^31 - 1)`, computed without the generator.
+    let z = |k: u64| {
+        let (mut res, mut base, mut e) = (23482349_u64, 950706376_u64, k);
+        while e != 0 {
+            if e & 1 == 1 {
+                res = res * base % 2147483647;
+            }
+            base = base * base % 2147483647;
+            e >>= 1;
+        }
+        res
+    };
+
+    let data = Lcg::new().generate(100000);
+    for k in [0, 1, 127, 4096, 65535, 99999] {
+        assert_eq!(data.get(k), Some(2 * z(k as u64 + 1) >= 2147483647));
+    }
+}
+
+#[test]
+fn test_qcg1() {
+    let mut generator = Qcg1::new();
+    check(
+        &generator.generate(1000),
+        [0x7c45bc2ad181c92e, 0xbbcda09d6753a09e],
+        502,
+    );
+    check(
+        &generator.generate(1000),
+        [0x97f85265da876c04, 0xd1c340138cc7ea2f],
+        494,
+    );
+}
+
+#[test]
+fn test_qcg2() {
+    let mut generator = Qcg2::new();
+    check(
+        &generator.generate(1000),
+        [0x8f4781601e803d09, 0xef9416e6af5e4e6b],
+        517,
+    );
+    check(
+        &generator.generate(1000),
+        [0x53bf5e81dde61f06, 0x7bce9a44737df2c6],
+        497,
+    );
+}
+
+#[test]
+fn test_ccg() {
+    let mut generator = Ccg::new();
+    check(
+        &generator.generate(1000),
+        [0xed2aacb681147abb, 0xacea9e69d63ca3f4],
+        500,
+    );
+    check(
+        &generator.generate(1000),
+        [0x9e61ed2c10d5bab4, 0x10ff0fa897d0c40f],
+        504,
+    );
+}
+
+#[test]
+fn test_xor() {
+    let mut generator = Xor::new();
+    check(
+        &generator.generate(1000),
+        [0xe491e51ced2780ca, 0xb383240077cbaa8a],
+        504,
+    );
+    check(
+        &generator.generate(1000),
+        [0xf465809c7156d61c, 0xaf2c08087fb5ee21],
+        469,
+    );
+}
+
+#[test]
+fn test_mod_exp() {
+    let mut generator = ModExp::new();
+    check(
+        &generator.generate(1000),
+        [0x82074c34cbab5448, 0xe6d3757221a61cb5],
+        517,
+    );
+    check(
+        &generator.generate(1000),
+        [0x55d799a25c64ff31, 0xd0be4e6cb1df1bd0],
+        517,
+    );
+}
+
+#[test]
+fn test_bbs() {
+    let mut generator = Bbs::new();
+    check(
+        &generator.generate(1000),
+        [0x1b260c795fae8bf4, 0xa8f2512aa29d986a],
+        488,
+    );
+    check(
+        &generator.generate(1000),
+        [0x109d3e2bde95e9d0, 0x40f18cd45c19be47],
+        486,
+    );
+}
+
+#[test]
+fn test_micali_schnorr() {
+    let mut generator = MicaliSchnorr::new();
+    check(
+        &generator.generate(1000),
+        [0x8a553a0596443783, 0xc7a64c3cd67150a1],
+        485,
+    );
+    check(
+        &generator.generate(1000),
+        [0x33d2721fe0eb67c8, 0x35cca9c99e2580f9],
+        517,
+    );
+}
+
+#[test]
+fn test_g_sha1() {
+    let mut generator = GSha1::new();
+    check(
+        &generator.generate(1000),
+        [0xf1ebcadc0afaab5a, 0xcddb42d438ba9258],
+        497,
+    );
+    check(
+        &generator.generate(1000),
+        [0x8c836968538ace99, 0x6f2882745839b0c6],
+        492,
+    );
+}
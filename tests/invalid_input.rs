@@ -0,0 +1,253 @@
+use nistrs::assessment::Assessment;
+use nistrs::battery::{Battery, BatteryConfig, TestKind};
+use nistrs::prelude::*;
+use nistrs::Error;
+
+#[test]
+fn test_empty() {
+    let data = BitsData::default();
+
+    assert!(frequency_test(&data).is_err());
+    assert!(block_frequency_test(&data, 10).is_err());
+    assert!(cumulative_sums_test(&data).is_err());
+    assert!(runs_test(&data).is_err());
+    assert!(longest_run_of_ones_test(&data).is_err());
+    assert!(rank_test(&data).is_err());
+    assert!(fft_test(&data).is_err());
+    assert!(non_overlapping_template_test(&data, 9).is_err());
+    assert!(overlapping_template_test(&data, 9).is_err());
+    assert!(universal_test(&data).is_err());
+    assert!(approximate_entropy_test(&data, 2).is_err());
+    assert!(random_excursions_test(&data).is_err());
+    assert!(random_excursions_variant_test(&data).is_err());
+    assert!(serial_test(&data, 2).is_err());
+    assert!(linear_complexity_test(&data, 500).is_err());
+
+    let report = Battery::new(BatteryConfig::default()).unwrap().run(&data);
+    assert_eq!(report.errors().count(), 15);
+    assert!(!report.passed());
+}
+
+#[test]
+fn test_tiny() {
+    let data = BitsData::from_text("1".to_string());
+
+    assert_eq!(frequency_test(&data).unwrap().1, 0.31731050786291415);
+    assert!(cumulative_sums_test(&data).is_ok());
+    assert!(runs_test(&data).is_ok());
+    assert!(fft_test(&data).is_ok());
+    assert_eq!(
+        universal_test(&data),
+        Err(Error::TooFewBits {
+            required: 387840,
+            actual: 1
+        })
+    );
+    assert!(matches!(
+        random_excursions_test(&data),
+        Err(Error::NotApplicable { .. })
+    ));
+
+    let report = Battery::new(BatteryConfig::default()).unwrap().run(&data);
+    assert_eq!(report.outcomes.len(), 15);
+    assert!(!report.passed());
+
+    let report = Battery::new(BatteryConfig {
+        tests: vec![TestKind::Frequency, TestKind::RandomExcursions],
+        ..Default::default()
+    })
+    .unwrap()
+    .run(&data);
+    assert_eq!(report.errors().count(), 1);
+    assert!(report.passed());
+}
+
+#[test]
+fn test_invalid_parameters() {
+    let data = BitsData::from_binary((0..256_u32).map(|x| (x * 37 % 251) as u8).collect());
+
+    assert_eq!(
+        serial_test(&data, 1),
+        Err(Error::InvalidParameter {
+            name: "m",
+            allowed: "2..=11".to_string()
+        })
+    );
+    assert!(serial_test(&data, 12).is_err());
+    assert!(serial_test(&data, 11).is_ok());
+
+    assert!(approximate_entropy_test(&data, 11).is_err());
+    assert!(approximate_entropy_test(&data, 0).is_ok());
+
+    assert!(linear_complexity_test(&data, 0).is_err());
+    assert_eq!(
+        linear_complexity_test(&data, 2049),
+        Err(Error::TooFewBits {
+            required: 2049,
+            actual: 2048
+        })
+    );
+
+    assert!(overlapping_template_test(&data, 0).is_err());
+    assert!(overlapping_template_test(&data, 65).is_err());
+    assert!(overlapping_template_test(&data, 9).is_ok());
+
+    let template = Template::new(0b1011, 4).unwrap();
+    assert_eq!(
+        overlapping_template_test_with(&data, template, 3, 2, PiMode::Exact),
+        Err(Error::InvalidParameter {
+            name: "block_size",
+            allowed: "4..".to_string()
+        })
+    );
+    assert_eq!(
+        overlapping_template_test_with(&data, template, 10, 8, PiMode::Exact),
+        Err(Error::InvalidParameter {
+            name: "k",
+            allowed: "1..=7".to_string()
+        })
+    );
+    assert_eq!(
+        overlapping_template_test_with(&data, template, 4096, 5, PiMode::Exact),
+        Err(Error::TooFewBits {
+            required: 4096,
+            actual: 2048
+        })
+    );
+    let report =
+        overlapping_template_test_report_with(&data, template, 32, 4, PiMode::Exact).unwrap();
+    assert_eq!(report.observed.len(), 5);
+    assert_eq!(report.observed.iter().sum::<f64>(), 64_f64);
+    assert!(report.warnings.is_empty());
+    let report =
+        overlapping_template_test_report_with(&data, template, 16, 4, PiMode::Exact).unwrap();
+    assert_eq!(report.observed.iter().sum::<f64>(), 128_f64);
+    assert_eq!(report.warnings.len(), 1);
+
+    // No block without occurrences of "1" is expected in a block of 100000 bits.
+    let zeros = BitsData::from_binary(vec![0; 125000]);
+    let template = Template::new(1, 1).unwrap();
+    assert!(matches!(
+        overlapping_template_test_with(&zeros, template, 100000, 1, PiMode::Approximation),
+        Err(Error::InvalidParameter {
+            name: "block_size",
+            ..
+        })
+    ));
+
+    assert!(block_frequency_test(&data, 0).is_err());
+    assert_eq!(
+        block_frequency_test(data.slice(..0), 10),
+        Err(Error::TooFewBits {
+            required: 10,
+            actual: 0
+        })
+    );
+    let templates = nistrs::non_overlapping_template::templates(9).unwrap();
+    assert!(non_overlapping_template_test(&data, 17).is_err());
+    assert!(non_overlapping_template_test_with(&data, templates, 0).is_err());
+    assert!(non_overlapping_template_test_with(&data, templates, 101).is_err());
+    assert_eq!(
+        non_overlapping_template_test_with(&data, templates, 228),
+        Err(Error::InvalidParameter {
+            name: "n_blocks",
+            allowed: "1..=100".to_string()
+        })
+    );
+    let reports = non_overlapping_template_test_report_with(&data, templates, 100).unwrap();
+    assert_eq!(reports.len(), 148);
+    assert_eq!(reports[0].1.observed.len(), 100);
+    assert_eq!(reports[0].1.warnings.len(), 1);
+    assert!(
+        non_overlapping_template_test_report_with(&data, templates, 50).unwrap()[0]
+            .1
+            .warnings
+            .is_empty()
+    );
+    assert_eq!(
+        non_overlapping_template_test_report(&data, 9).unwrap()[0]
+            .observed
+            .len(),
+        8
+    );
+    assert!(nistrs::non_overlapping_template::templates(1).is_err());
+
+    assert!(Template::new(1, 65).is_err());
+    let long = Template::new(1, 64).unwrap();
+    assert_eq!(
+        non_overlapping_template_test_with(data.slice(..500), &[long], 8),
+        Err(Error::TooFewBits {
+            required: 512,
+            actual: 500
+        })
+    );
+    assert_eq!(
+        non_overlapping_template_test_with(&data, &[], 8),
+        Ok(vec![])
+    );
+
+    assert!(rank_test_with(&data, 1, 8).is_err());
+    assert!(rank_test_with(&data, 6, 65).is_err());
+    assert_eq!(
+        rank_test_with(&data, 8, 8),
+        Err(Error::TooFewBits {
+            required: 2432,
+            actual: 2048
+        })
+    );
+    assert!(matches!(
+        rank_test_with(&data, 6, 8),
+        Err(Error::InvalidParameter { name: "m", .. })
+    ));
+    assert!(rank_test_with(&data, 4, 4).is_ok());
+    assert_eq!(
+        rank_test_with(&data, usize::MAX / 2, 4),
+        Err(Error::InvalidParameter {
+            name: "m",
+            allowed: format!("2..={}", usize::MAX / (38 * 4))
+        })
+    );
+    assert!(matches!(
+        rank_test_with(&data, usize::MAX / 64 + 1, 64),
+        Err(Error::InvalidParameter { name: "m", .. })
+    ));
+
+    // Matrices of very different sides almost always have full rank, expected counts of lower ranks are zero.
+    let large = BitsData::from_binary((0..12500_u32).map(|x| (x * 37 % 251) as u8).collect());
+    for (m, q) in [(64, 2), (2, 64), (200, 2), (1000, 2)] {
+        assert!(
+            matches!(
+                rank_test_with(&large, m, q),
+                Err(Error::InvalidParameter { name: "m", .. })
+            ),
+            "{} x {}",
+            m,
+            q
+        );
+    }
+    assert!(rank_test_with(&large, 6, 8).is_ok());
+
+    assert!(matches!(
+        Assessment::new(BatteryConfig::default(), usize::MAX / 2, 3),
+        Err(Error::InvalidParameter {
+            name: "streams",
+            ..
+        })
+    ));
+    assert!(Assessment::new(BatteryConfig::default(), usize::MAX / 2, 2).is_ok());
+}
+
+#[test]
+fn test_text() {
+    assert_eq!(
+        BitsData::try_from_text("01\n 10x1"),
+        Err(Error::InvalidCharacter {
+            offset: 6,
+            byte: b'x'
+        })
+    );
+    assert_eq!(
+        BitsData::try_from_text(" 0101 \r\n1111\n"),
+        Ok(BitsData::from_text("01011111".to_string()))
+    );
+}
@@ -34,7 +34,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(frequency_test(&res).1, 0.604458));
+            assert!(equal_results(frequency_test(&res).unwrap().1, 0.604458));
         }
 
         #[test]
@@ -55,7 +55,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(runs_test(&res).1, 0.309757));
+            assert!(equal_results(runs_test(&res).unwrap().1, 0.309757));
         }
 
         #[test]
@@ -85,7 +85,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(fft_test(&res).1, 0.163062));
+            assert!(equal_results(fft_test(&res).unwrap().1, 0.163062));
         }
 
         #[test]
@@ -246,6 +246,24 @@ mod test {
             assert!(equal_results(ret[147].1, 0.541378));
         }
 
+        #[test]
+        fn test_non_overlapping_with() {
+            use nistrs::non_overlapping_template::{non_overlapping_template_test_with, templates};
+
+            let res = load_sequnce().unwrap();
+
+            let all = templates(9).unwrap();
+            let chosen = [all[10], all[145], all[0]];
+            let ret = non_overlapping_template_test_with(&res, &chosen, 8).unwrap();
+
+            assert_eq!(ret.len(), 3);
+            assert_eq!(ret[2].0.to_string(), "000000001");
+            assert!(ret.iter().zip(chosen).all(|(x, t)| x.0 == t));
+            assert!(equal_results(ret[0].1 .1, 0.001239));
+            assert!(equal_results(ret[1].1 .1, 0.017200));
+            assert!(equal_results(ret[2].1 .1, 0.496601));
+        }
+
         #[test]
         fn test_overlapping() {
             use nistrs::overlapping_template::overlapping_template_test;
@@ -253,18 +271,62 @@ mod test {
             let res = load_sequnce().unwrap();
 
             assert!(equal_results(
-                overlapping_template_test(&res, 9).1,
+                overlapping_template_test(&res, 9).unwrap().1,
                 0.339426
             ))
         }
 
+        #[test]
+        fn test_overlapping_exact() {
+            use nistrs::overlapping_template::{overlapping_template_test_with, PiMode};
+            use nistrs::Template;
+
+            let res = load_sequnce().unwrap();
+
+            let ones = Template::new(0x1FF, 9).unwrap();
+            assert!(equal_results(
+                overlapping_template_test_with(&res, ones, 1032, 5, PiMode::Exact)
+                    .unwrap()
+                    .1,
+                0.249749
+            ));
+        }
+
+        #[test]
+        fn test_overlapping_with() {
+            use nistrs::overlapping_template::{
+                overlapping_template_test, overlapping_template_test_report_with, PiMode,
+            };
+            use nistrs::Template;
+
+            let res = load_sequnce().unwrap();
+
+            let ones = Template::new(0x1FF, 9).unwrap();
+            let report =
+                overlapping_template_test_report_with(&res, ones, 1032, 5, PiMode::Approximation)
+                    .unwrap();
+            assert_eq!(report.result(), overlapping_template_test(&res, 9).unwrap());
+            assert!(report.warnings.is_empty());
+
+            let report =
+                overlapping_template_test_report_with(&res, ones, 2048, 8, PiMode::Exact).unwrap();
+            assert_eq!(report.parameter("n"), Some(488_f64));
+            assert_eq!(report.observed.len(), 9);
+
+            // Probabilities are tabulated only for the standard parameters.
+            let approximation =
+                overlapping_template_test_report_with(&res, ones, 2048, 8, PiMode::Approximation)
+                    .unwrap();
+            assert_eq!(report, approximation);
+        }
+
         #[test]
         fn test_universal() {
             use nistrs::universal::universal_test;
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(universal_test(&res).1, 0.411079))
+            assert!(equal_results(universal_test(&res).unwrap().1, 0.411079))
         }
 
         #[test]
@@ -273,7 +335,10 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(linear_complexity_test(&res, 500).1, 0.309412))
+            assert!(equal_results(
+                linear_complexity_test(&res, 500).unwrap().1,
+                0.309412
+            ))
         }
 
         #[test]
@@ -282,7 +347,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            let ret = serial_test(&res, 16);
+            let ret = serial_test(&res, 16).unwrap();
 
             assert!(equal_results(ret[0].1, 0.760793));
         }
@@ -294,7 +359,7 @@ mod test {
             let res = load_sequnce().unwrap();
 
             assert!(equal_results(
-                approximate_entropy_test(&res, 10).1,
+                approximate_entropy_test(&res, 10).unwrap().1,
                 0.982885
             ));
         }
@@ -305,7 +370,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            let ret = cumulative_sums_test(&res);
+            let ret = cumulative_sums_test(&res).unwrap();
 
             assert!(equal_results(ret[0].1, 0.451231));
 
@@ -313,23 +378,126 @@ mod test {
         }
 
         #[test]
-        #[should_panic]
         fn test_random_excursions() {
             use nistrs::random_excursions::random_excursions_test;
+            use nistrs::Error;
 
             let res = load_sequnce().unwrap();
 
-            random_excursions_test(&res).unwrap();
+            assert!(matches!(
+                random_excursions_test(&res),
+                Err(Error::NotApplicable { required: 500, .. })
+            ));
         }
 
         #[test]
-        #[should_panic]
         fn test_random_excursions_variant() {
             use nistrs::random_excursions_variant::random_excursions_variant_test;
+            use nistrs::Error;
+
+            let res = load_sequnce().unwrap();
+
+            assert!(matches!(
+                random_excursions_variant_test(&res),
+                Err(Error::NotApplicable { required: 500, .. })
+            ));
+        }
+
+        #[test]
+        fn test_battery() {
+            use nistrs::battery::{Battery, BatteryConfig, TestKind};
+            use nistrs::Error;
+
+            let res = load_sequnce().unwrap();
+
+            let report = Battery::new(BatteryConfig::default()).unwrap().run(&res);
+
+            assert_eq!(report.outcomes.len(), 15);
+            assert_eq!(
+                report.reports().count(),
+                1 + 1 + 2 + 1 + 1 + 1 + 1 + 148 + 1 + 1 + 1 + 2 + 1
+            );
+
+            let freq = report.get(TestKind::Frequency).unwrap();
+            assert!(equal_results(
+                freq.result.as_ref().unwrap()[0].p_value,
+                0.604458
+            ));
+
+            let serial = report.get(TestKind::Serial).unwrap();
+            assert!(equal_results(
+                serial.result.as_ref().unwrap()[0].p_value,
+                0.760793
+            ));
+
+            let errors: Vec<_> = report.errors().map(|x| x.0).collect();
+            assert_eq!(
+                errors,
+                [
+                    TestKind::RandomExcursions,
+                    TestKind::RandomExcursionsVariant
+                ]
+            );
+            assert!(report
+                .errors()
+                .all(|x| matches!(x.1, Error::NotApplicable { .. })));
+
+            let failures: Vec<_> = report.failures().map(|x| x.1.p_value).collect();
+            assert_eq!(failures.len(), 3);
+            assert!(equal_results(failures[0], 0.001239));
+            assert!(equal_results(failures[1], 0.006495));
+            assert!(equal_results(failures[2], 0.008562));
+            assert!(!report.passed());
+        }
+
+        #[test]
+        fn test_assessment() {
+            use nistrs::assessment::Assessment;
+            use nistrs::battery::{BatteryConfig, TestKind};
 
             let res = load_sequnce().unwrap();
 
-            random_excursions_variant_test(&res).unwrap();
+            let config = BatteryConfig {
+                tests: vec![
+                    TestKind::Frequency,
+                    TestKind::CumulativeSums,
+                    TestKind::RandomExcursions,
+                ],
+                ..Default::default()
+            };
+            let report = Assessment::new(config, 100000, 10)
+                .unwrap()
+                .run(&res)
+                .unwrap();
+
+            assert_eq!(report.sequences.len(), 10);
+            assert_eq!(report.summaries.len(), 1 + 2 + 8);
+
+            for summary in report.summaries.iter().take(3) {
+                assert_eq!(summary.total(), 10);
+                assert_eq!(summary.histogram.iter().sum::<usize>(), 10);
+                assert!((summary.passed_range.0 - 8.956072).abs() < 1e-6);
+                assert!((summary.passed_range.1 - 10.843928).abs() < 1e-6);
+                assert!(summary.uniformity.is_some());
+            }
+
+            let freq = &report.summaries[0];
+            assert_eq!(
+                freq.passed,
+                report
+                    .sequences
+                    .iter()
+                    .filter(|x| x.outcomes[0].result.as_ref().unwrap()[0].passed())
+                    .count()
+            );
+
+            let excursions = &report.summaries[3];
+            assert_eq!(excursions.test, TestKind::RandomExcursions);
+            assert!(excursions.total() < 10);
+            assert_eq!(
+                excursions.histogram.iter().sum::<usize>(),
+                excursions.total()
+            );
         }
     }
 }
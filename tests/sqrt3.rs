@@ -34,7 +34,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(frequency_test(&res).1, 0.546820));
+            assert!(equal_results(frequency_test(&res).unwrap().1, 0.546820));
         }
 
         #[test]
@@ -55,7 +55,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(runs_test(&res).1, 0.229863));
+            assert!(equal_results(runs_test(&res).unwrap().1, 0.229863));
         }
 
         #[test]
@@ -85,7 +85,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(fft_test(&res).1, 0.509824));
+            assert!(equal_results(fft_test(&res).unwrap().1, 0.509824));
         }
 
         #[test]
@@ -253,18 +253,34 @@ mod test {
             let res = load_sequnce().unwrap();
 
             assert!(equal_results(
-                overlapping_template_test(&res, 9).1,
+                overlapping_template_test(&res, 9).unwrap().1,
                 0.070981
             ))
         }
 
+        #[test]
+        fn test_overlapping_exact() {
+            use nistrs::overlapping_template::{overlapping_template_test_with, PiMode};
+            use nistrs::Template;
+
+            let res = load_sequnce().unwrap();
+
+            let ones = Template::new(0x1FF, 9).unwrap();
+            assert!(equal_results(
+                overlapping_template_test_with(&res, ones, 1032, 5, PiMode::Exact)
+                    .unwrap()
+                    .1,
+                0.069083
+            ));
+        }
+
         #[test]
         fn test_universal() {
             use nistrs::universal::universal_test;
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(universal_test(&res).1, 0.150578))
+            assert!(equal_results(universal_test(&res).unwrap().1, 0.150578))
         }
 
         #[test]
@@ -273,7 +289,10 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            assert!(equal_results(linear_complexity_test(&res, 500).1, 0.341994))
+            assert!(equal_results(
+                linear_complexity_test(&res, 500).unwrap().1,
+                0.341994
+            ))
         }
 
         #[test]
@@ -282,7 +301,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            let ret = serial_test(&res, 16);
+            let ret = serial_test(&res, 16).unwrap();
 
             assert!(equal_results(ret[0].1, 0.180826));
             assert!(equal_results(ret[1].1, 0.230061));
@@ -295,7 +314,7 @@ mod test {
             let res = load_sequnce().unwrap();
 
             assert!(equal_results(
-                approximate_entropy_test(&res, 10).1,
+                approximate_entropy_test(&res, 10).unwrap().1,
                 0.154929
             ));
         }
@@ -306,7 +325,7 @@ mod test {
 
             let res = load_sequnce().unwrap();
 
-            let ret = cumulative_sums_test(&res);
+            let ret = cumulative_sums_test(&res).unwrap();
 
             assert!(equal_results(ret[0].1, 0.918218));
 
@@ -0,0 +1,90 @@
+use std::fs;
+
+use nistrs::cusum::{cumulative_sums_test_report, CumulativeSumsAccumulator};
+use nistrs::freq::{frequency_test_report, FrequencyAccumulator};
+use nistrs::runs::{runs_test, runs_test_report, RunsAccumulator};
+use nistrs::{BitsData, Error};
+
+fn load_bytes() -> Vec<u8> {
+    fs::read("./tests/files/data.sha1").unwrap()
+}
+
+/// Split `bytes` into chunks of irregular lengths.
+fn chunks(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = bytes;
+    let mut len = 0;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        len = (len * 7 + 3) % 5003;
+        let (chunk, tail) = rest.split_at(len.min(rest.len()));
+        rest = tail;
+        Some(chunk)
+    })
+}
+
+#[test]
+fn test_bytes() {
+    let bytes = load_bytes();
+    let data = BitsData::from_binary(bytes.clone());
+
+    let mut freq = FrequencyAccumulator::new();
+    let mut runs = RunsAccumulator::new();
+    let mut cusum = CumulativeSumsAccumulator::new();
+    for chunk in chunks(&bytes) {
+        freq.update(chunk);
+        runs.update(chunk);
+        cusum.update(chunk);
+    }
+
+    assert_eq!(freq.len(), data.len());
+    assert_eq!(freq.finalize_report(), frequency_test_report(&data));
+    assert_eq!(runs.finalize_report(), runs_test_report(&data));
+    assert_eq!(cusum.finalize_report(), cumulative_sums_test_report(&data));
+}
+
+#[test]
+fn test_bits() {
+    let data = BitsData::from_binary(load_bytes());
+    let data = data.slice(3..data.len() - 5);
+
+    let mut freq = FrequencyAccumulator::new();
+    let mut runs = RunsAccumulator::new();
+    let mut cusum = CumulativeSumsAccumulator::new();
+    let mut begin = 0;
+    for len in [1, 63, 64, 65, 1000, 12345].iter().cycle() {
+        let end = data.len().min(begin + len);
+        freq.update_bits(data.slice(begin..end));
+        runs.update_bits(data.slice(begin..end));
+        cusum.update_bits(data.slice(begin..end));
+
+        begin = end;
+        if begin == data.len() {
+            break;
+        }
+    }
+
+    assert_eq!(runs.len(), data.len());
+    assert_eq!(freq.finalize_report(), frequency_test_report(data));
+    assert_eq!(runs.finalize_report(), runs_test_report(data));
+    assert_eq!(cusum.finalize_report(), cumulative_sums_test_report(data));
+}
+
+#[test]
+fn test_edge_cases() {
+    let empty = Err(Error::TooFewBits {
+        required: 1,
+        actual: 0,
+    });
+    assert_eq!(FrequencyAccumulator::new().finalize(), empty);
+    assert_eq!(RunsAccumulator::new().finalize(), empty);
+    assert!(CumulativeSumsAccumulator::new().finalize().is_err());
+
+    let mut runs = RunsAccumulator::new();
+    runs.update(&[0xFF; 100]);
+    let data = BitsData::from_binary(vec![0xFF; 100]);
+    assert_eq!(runs.finalize(), Ok((false, 0.5)));
+    assert_eq!(runs.finalize(), runs_test(&data));
+}